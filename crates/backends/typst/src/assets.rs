@@ -0,0 +1,92 @@
+//! Static discovery of the files a plate loads.
+//!
+//! Walks the parsed Typst syntax tree for calls to the file-loading functions
+//! (`image`, `read`, `json`, ...) and `include`, collecting string-literal
+//! paths. Paths computed at runtime (e.g. `image(data.logo)`) cannot be
+//! resolved statically and are not reported.
+
+use typst::syntax::{ast, SyntaxNode};
+
+/// Typst functions whose first positional argument is a file path.
+const FILE_LOADERS: &[&str] = &[
+    "image",
+    "read",
+    "json",
+    "yaml",
+    "toml",
+    "csv",
+    "xml",
+    "cbor",
+    "bibliography",
+];
+
+/// Quill-relative paths referenced by string literal in `plate`, deduplicated
+/// in first-reference order.
+pub(crate) fn referenced_assets(plate: &str) -> Vec<String> {
+    let root = typst::syntax::parse(plate);
+    let mut paths = Vec::new();
+    collect(&root, &mut paths);
+    paths
+}
+
+fn collect(node: &SyntaxNode, paths: &mut Vec<String>) {
+    let literal = if let Some(call) = node.cast::<ast::FuncCall>() {
+        match call.callee() {
+            ast::Expr::Ident(name) if FILE_LOADERS.contains(&name.as_str()) => {
+                call.args().items().find_map(|arg| match arg {
+                    ast::Arg::Pos(ast::Expr::Str(s)) => Some(s.get()),
+                    _ => None,
+                })
+            }
+            _ => None,
+        }
+    } else if let Some(include) = node.cast::<ast::ModuleInclude>() {
+        match include.source() {
+            ast::Expr::Str(s) => Some(s.get()),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    if let Some(path) = literal {
+        // Plate paths resolve against the quill root, where `main.typ` lives.
+        let path = path.trim_start_matches('/').trim_start_matches("./");
+        if !path.is_empty() && !path.starts_with('@') && !paths.iter().any(|p| p == path) {
+            paths.push(path.to_string());
+        }
+    }
+
+    for child in node.children() {
+        collect(child, paths);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_literal_paths() {
+        let plate = r#"
+#image("assets/logo.png", width: 2cm)
+#let cfg = json("/assets/config.json")
+#include "./parts/footer.typ"
+#image("assets/logo.png")
+"#;
+        assert_eq!(
+            referenced_assets(plate),
+            vec!["assets/logo.png", "assets/config.json", "parts/footer.typ"]
+        );
+    }
+
+    #[test]
+    fn test_ignores_dynamic_paths_and_other_calls() {
+        let plate = r#"
+#import "@preview/example:0.1.0": *
+#image(data.logo)
+#text("assets/not-a-path.png")
+"#;
+        assert!(referenced_assets(plate).is_empty());
+    }
+}
@@ -5,38 +5,60 @@
 //! ## Functions
 //!
 //! - [`compile_to_pdf()`] - Compile Typst to PDF format
+//! - [`compile_to_pdf_with_standard()`] - Compile Typst to PDF/A or another PDF standard
+//! - [`compile_to_pdf_to_writer()`] - Compile Typst to PDF, writing into an `io::Write` sink
+//! - [`compile_to_pdf_to_writer_with_standard()`] - Like `compile_to_pdf_to_writer`, for a PDF standard
 //! - [`compile_to_svg()`] - Compile Typst to SVG format (one file per page)
+//! - [`compile_to_svg_combined()`] - Compile Typst to a single SVG with pages stacked vertically
 //! - [`compile_to_png()`] - Compile Typst to PNG format (one image per page) at a given PPI
+//! - [`compile_to_txt()`] - Compile Typst and extract its text in reading order
 //!
 //! ## Process
 //!
-//! 1. Creates a `QuillWorld` with the quill's assets and packages
+//! 1. Creates a `QuillWorld` with the quill's assets and packages, leaving out
+//!    assets Quill.yaml limits to other output formats
 //! 2. Compiles the Typst document using the Typst compiler
 //! 3. Converts to target format (PDF, SVG, or PNG)
 //! 4. Returns output bytes
 //!
 //! The output bytes can be written to a file or returned directly to the caller.
 
+use std::io::Write;
+
 use typst::diag::Warned;
 use typst::layout::PagedDocument;
-use typst_pdf::PdfOptions;
+use typst_pdf::{PdfOptions, PdfStandards};
 
 use crate::error_mapping::map_typst_errors;
 use crate::world::QuillWorld;
 use quillmark_core::{
-    Artifact, Diagnostic, OutputFormat, QuillSource, RenderError, RenderResult, Severity,
+    Artifact, Diagnostic, OutputFormat, PdfStandard, QuillSource, RenderError, RenderResult,
+    Severity,
 };
 
-/// Internal compilation function
-fn compile_document(world: &QuillWorld) -> Result<PagedDocument, RenderError> {
+/// Prefix of the warning Typst emits when a requested font family is not in
+/// the font book. Typst falls back to another family in that case.
+const UNKNOWN_FONT_FAMILY: &str = "unknown font family: ";
+
+/// Internal compilation function. Returns the document and the font families
+/// the document requested but the quill does not provide.
+fn compile_document(world: &QuillWorld) -> Result<(PagedDocument, Vec<String>), RenderError> {
     let Warned { output, warnings } = typst::compile::<PagedDocument>(world);
 
+    let mut missing_fonts = Vec::new();
     for warning in warnings {
-        eprintln!("Warning: {}", warning.message);
+        match warning.message.strip_prefix(UNKNOWN_FONT_FAMILY) {
+            Some(family) => {
+                if !missing_fonts.iter().any(|f| f == family) {
+                    missing_fonts.push(family.to_string());
+                }
+            }
+            None => log::warn!("{}", warning.message),
+        }
     }
 
     match output {
-        Ok(doc) => Ok(doc),
+        Ok(doc) => Ok((doc, missing_fonts)),
         Err(errors) => {
             let diagnostics = map_typst_errors(&errors, world);
             Err(RenderError::CompilationFailed { diags: diagnostics })
@@ -50,22 +72,59 @@ pub fn compile_to_document(
     plated_content: &str,
     json_data: &str,
 ) -> Result<PagedDocument, RenderError> {
-    let world = QuillWorld::new_with_data(source, plated_content, json_data).map_err(|e| {
-        RenderError::EngineCreation {
-            diag: Box::new(
-                Diagnostic::new(
-                    Severity::Error,
-                    format!("Failed to create Typst compilation environment: {}", e),
-                )
-                .with_code("typst::world_creation".to_string())
-                .with_source(e.as_ref()),
-            ),
-        }
-    })?;
+    compile_to_document_with_missing_fonts(source, plated_content, json_data)
+        .map(|(document, _)| document)
+}
+
+/// Like [`compile_to_document()`], also returning the font families the
+/// document asked for that the quill does not provide (lowercased, as Typst
+/// reports them). Typst substitutes a fallback font for each.
+pub fn compile_to_document_with_missing_fonts(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+) -> Result<(PagedDocument, Vec<String>), RenderError> {
+    compile_to_document_for_format(source, plated_content, json_data, None)
+}
+
+/// Like [`compile_to_document_with_missing_fonts()`], for a document that will
+/// only be exported to `format`: assets Quill.yaml limits to other formats
+/// are left out of the compilation. `None` keeps every asset.
+pub fn compile_to_document_for_format(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+    format: Option<OutputFormat>,
+) -> Result<(PagedDocument, Vec<String>), RenderError> {
+    let world =
+        QuillWorld::new_with_data(source, plated_content, json_data, format).map_err(|e| {
+            RenderError::EngineCreation {
+                diag: Box::new(
+                    Diagnostic::new(
+                        Severity::Error,
+                        format!("Failed to create Typst compilation environment: {}", e),
+                    )
+                    .with_code("typst::world_creation".to_string())
+                    .with_source(e.as_ref()),
+                ),
+            }
+        })?;
 
     compile_document(&world)
 }
 
+/// Compile a document that will only be exported to `format`, dropping the
+/// missing-font report.
+fn compile_for_format(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+    format: OutputFormat,
+) -> Result<PagedDocument, RenderError> {
+    compile_to_document_for_format(source, plated_content, json_data, Some(format))
+        .map(|(document, _)| document)
+}
+
 /// Compiles a Typst document to PDF format with JSON data injection.
 ///
 /// This function creates a `@local/quillmark-helper:0.1.0` package containing
@@ -75,19 +134,117 @@ pub fn compile_to_pdf(
     plated_content: &str,
     json_data: &str,
 ) -> Result<Vec<u8>, RenderError> {
-    let document = compile_to_document(source, plated_content, json_data)?;
+    compile_to_pdf_with_standard(source, plated_content, json_data, None)
+}
+
+/// Like [`compile_to_pdf()`], exporting the PDF in the archival conformance
+/// level `pdf_standard` when set.
+pub fn compile_to_pdf_with_standard(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+    pdf_standard: Option<PdfStandard>,
+) -> Result<Vec<u8>, RenderError> {
+    let document = compile_for_format(source, plated_content, json_data, OutputFormat::Pdf)?;
+    export_pdf(&document, pdf_standard)
+}
 
-    let pdf = typst_pdf::pdf(&document, &PdfOptions::default()).map_err(|e| {
-        RenderError::CompilationFailed {
-            diags: vec![Diagnostic::new(
+/// Map a core PDF standard to Typst's export setting.
+fn typst_pdf_standard(standard: PdfStandard) -> typst_pdf::PdfStandard {
+    match standard {
+        PdfStandard::A1b => typst_pdf::PdfStandard::A_1b,
+        PdfStandard::A2b => typst_pdf::PdfStandard::A_2b,
+        PdfStandard::A2u => typst_pdf::PdfStandard::A_2u,
+        PdfStandard::A3b => typst_pdf::PdfStandard::A_3b,
+        PdfStandard::A3u => typst_pdf::PdfStandard::A_3u,
+    }
+}
+
+/// Export a compiled document to PDF, enforcing `pdf_standard` when set.
+///
+/// PDF/A requires every font to be embedded and the document to carry a date
+/// (`#set document(date: ..)`); Typst rejects the export otherwise, and each
+/// such error is reported as a `typst::pdf_standard` diagnostic.
+fn export_pdf(
+    document: &PagedDocument,
+    pdf_standard: Option<PdfStandard>,
+) -> Result<Vec<u8>, RenderError> {
+    let mut options = PdfOptions::default();
+    if let Some(standard) = pdf_standard {
+        options.standards = PdfStandards::new(&[typst_pdf_standard(standard)]).map_err(|e| {
+            RenderError::CompilationFailed {
+                diags: vec![Diagnostic::new(
+                    Severity::Error,
+                    format!("Unsupported PDF standard {}: {}", standard, e),
+                )
+                .with_code("typst::pdf_standard".to_string())],
+            }
+        })?;
+    }
+
+    typst_pdf::pdf(document, &options).map_err(|errors| {
+        let diags = match pdf_standard {
+            Some(standard) => errors
+                .iter()
+                .map(|error| {
+                    Diagnostic::new(
+                        Severity::Error,
+                        format!("{} export failed: {}", standard, error.message),
+                    )
+                    .with_code("typst::pdf_standard".to_string())
+                    .with_hint(
+                        "PDF/A needs fonts whose license permits embedding and a document date set with `#set document(date: ..)`"
+                            .to_string(),
+                    )
+                })
+                .collect(),
+            None => vec![Diagnostic::new(
                 Severity::Error,
-                format!("PDF generation failed: {:?}", e),
+                format!("PDF generation failed: {:?}", errors),
             )
             .with_code("typst::pdf_generation".to_string())],
-        }
-    })?;
+        };
+        RenderError::CompilationFailed { diags }
+    })
+}
 
-    Ok(pdf)
+/// Compiles a Typst document to PDF and writes the bytes to `writer`.
+///
+/// Typst produces the PDF as a single buffer, so this currently writes it
+/// through in one call; callers can target a file or socket without keeping
+/// their own copy.
+pub fn compile_to_pdf_to_writer<W: Write>(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+    writer: W,
+) -> Result<(), RenderError> {
+    compile_to_pdf_to_writer_with_standard(source, plated_content, json_data, None, writer)
+}
+
+/// Like [`compile_to_pdf_to_writer()`], exporting the PDF in the archival
+/// conformance level `pdf_standard` when set.
+pub fn compile_to_pdf_to_writer_with_standard<W: Write>(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+    pdf_standard: Option<PdfStandard>,
+    mut writer: W,
+) -> Result<(), RenderError> {
+    let pdf = compile_to_pdf_with_standard(source, plated_content, json_data, pdf_standard)?;
+    writer
+        .write_all(&pdf)
+        .and_then(|_| writer.flush())
+        .map_err(|e| RenderError::OutputFailed {
+            diag: Box::new(
+                Diagnostic::new(
+                    Severity::Error,
+                    format!("Failed to write PDF output: {}", e),
+                )
+                .with_code("typst::pdf_write".to_string())
+                .with_source(&e),
+            ),
+        })
 }
 
 /// Compiles a Typst document to SVG format with JSON data injection.
@@ -99,7 +256,7 @@ pub fn compile_to_svg(
     plated_content: &str,
     json_data: &str,
 ) -> Result<Vec<Vec<u8>>, RenderError> {
-    let document = compile_to_document(source, plated_content, json_data)?;
+    let document = compile_for_format(source, plated_content, json_data, OutputFormat::Svg)?;
 
     let mut pages = Vec::new();
     for page in &document.pages {
@@ -110,6 +267,28 @@ pub fn compile_to_svg(
     Ok(pages)
 }
 
+/// Compiles a Typst document to a single SVG with all pages stacked
+/// vertically.
+///
+/// The canvas is as wide as the widest page and as tall as all pages
+/// combined. A document with no pages yields an empty `<svg/>`.
+pub fn compile_to_svg_combined(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+) -> Result<Vec<u8>, RenderError> {
+    let document = compile_for_format(source, plated_content, json_data, OutputFormat::Svg)?;
+    Ok(svg_combined(&document))
+}
+
+/// Stack every page of `document` into one SVG document.
+fn svg_combined(document: &PagedDocument) -> Vec<u8> {
+    if document.pages.is_empty() {
+        return br#"<svg xmlns="http://www.w3.org/2000/svg"/>"#.to_vec();
+    }
+    typst_svg::svg_merged(document, typst::layout::Abs::zero()).into_bytes()
+}
+
 /// Default pixels per inch for PNG rendering (2x at 72pt/inch).
 const DEFAULT_PPI: f32 = 144.0;
 
@@ -129,7 +308,7 @@ pub fn compile_to_png(
     json_data: &str,
     ppi: Option<f32>,
 ) -> Result<Vec<Vec<u8>>, RenderError> {
-    let document = compile_to_document(source, plated_content, json_data)?;
+    let document = compile_for_format(source, plated_content, json_data, OutputFormat::Png)?;
 
     let ppi = ppi.unwrap_or(DEFAULT_PPI);
 
@@ -157,6 +336,7 @@ pub fn render_document_pages(
     pages: Option<&[usize]>,
     format: OutputFormat,
     ppi: Option<f32>,
+    pdf_standard: Option<PdfStandard>,
 ) -> Result<RenderResult, RenderError> {
     // PDF does not support selective page rendering
     if format == OutputFormat::Pdf && pages.is_some() {
@@ -202,6 +382,7 @@ pub fn render_document_pages(
                 .map(|idx| Artifact {
                     bytes: typst_svg::svg(&document.pages[idx]).into_bytes(),
                     output_format: OutputFormat::Svg,
+                    page: Some(idx),
                 })
                 .collect();
             Ok(RenderResult::new(artifacts, OutputFormat::Svg))
@@ -223,36 +404,302 @@ pub fn render_document_pages(
                 artifacts.push(Artifact {
                     bytes: png_data,
                     output_format: OutputFormat::Png,
+                    page: Some(idx),
                 });
             }
             Ok(RenderResult::new(artifacts, OutputFormat::Png))
         }
         OutputFormat::Pdf => {
-            let pdf = typst_pdf::pdf(document, &PdfOptions::default()).map_err(|e| {
-                RenderError::CompilationFailed {
-                    diags: vec![Diagnostic::new(
-                        Severity::Error,
-                        format!("PDF generation failed: {:?}", e),
-                    )
-                    .with_code("typst::pdf_generation".to_string())],
-                }
-            })?;
+            let pdf = export_pdf(document, pdf_standard)?;
             Ok(RenderResult::new(
                 vec![Artifact {
                     bytes: pdf,
                     output_format: OutputFormat::Pdf,
+                    page: None,
                 }],
                 OutputFormat::Pdf,
             ))
         }
-        OutputFormat::Txt => Err(RenderError::FormatNotSupported {
-            diag: Box::new(
-                Diagnostic::new(
-                    Severity::Error,
-                    "TXT output is not supported for Typst".into(),
-                )
-                .with_code("typst::format_not_supported".to_string()),
+        OutputFormat::Txt => {
+            let pages: Vec<_> = selected_indices
+                .into_iter()
+                .map(|idx| &document.pages[idx])
+                .collect();
+            Ok(RenderResult::new(
+                vec![Artifact {
+                    bytes: pages_text(&pages).into_bytes(),
+                    output_format: OutputFormat::Txt,
+                    page: None,
+                }],
+                OutputFormat::Txt,
+            ))
+        }
+    }
+}
+
+/// Compiles a Typst document and extracts its text in reading order.
+///
+/// Returns one UTF-8 string for the whole document; see [`pages_text`].
+pub fn compile_to_txt(
+    source: &QuillSource,
+    plated_content: &str,
+    json_data: &str,
+) -> Result<String, RenderError> {
+    let document = compile_for_format(source, plated_content, json_data, OutputFormat::Txt)?;
+    let pages: Vec<_> = document.pages.iter().collect();
+    Ok(pages_text(&pages))
+}
+
+/// Plain text of `pages`: text runs in layout order, a newline between
+/// lines, and a blank line between pages. Images, shapes, and other
+/// non-text items are skipped.
+fn pages_text(pages: &[&typst::layout::Page]) -> String {
+    use typst::layout::{Abs, Frame, FrameItem, Point};
+
+    fn collect(frame: &Frame, offset: Point, last_y: &mut Option<Abs>, out: &mut String) {
+        for (pos, item) in frame.items() {
+            let pos = offset + *pos;
+            match item {
+                FrameItem::Group(group) => collect(&group.frame, pos, last_y, out),
+                FrameItem::Text(text) => {
+                    // Runs on one baseline join directly; a new baseline is a
+                    // new line.
+                    if last_y.is_some_and(|y| (y - pos.y).abs() > Abs::pt(0.5)) {
+                        out.push('\n');
+                    }
+                    *last_y = Some(pos.y);
+                    out.push_str(&text.text);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pages
+        .iter()
+        .map(|page| {
+            let mut out = String::new();
+            collect(&page.frame, Point::zero(), &mut None, &mut out);
+            out
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quillmark_core::FileTreeNode;
+    use std::collections::HashMap;
+
+    fn test_source() -> QuillSource {
+        let quill_yaml = "quill:\n  name: \"svg_test\"\n  version: \"1.0\"\n  backend: \"typst\"\n  description: \"Test\"\n";
+        let mut files = HashMap::new();
+        files.insert(
+            "Quill.yaml".to_string(),
+            FileTreeNode::File {
+                contents: quill_yaml.as_bytes().to_vec(),
+            },
+        );
+        QuillSource::from_tree(FileTreeNode::Directory { files }).expect("load source")
+    }
+
+    #[test]
+    fn test_svg_combined_stacks_pages() {
+        let plate = "#set page(width: 100pt, height: 50pt)\nOne\n#page(width: 200pt)[Two]";
+        let svg = compile_to_svg_combined(&test_source(), plate, "{}").unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert_eq!(svg.matches("<svg").count(), 1);
+        assert!(
+            svg.contains(r#"width="200pt""#),
+            "max page width: {}",
+            &svg[..200]
+        );
+        assert!(
+            svg.contains(r#"height="100pt""#),
+            "summed height: {}",
+            &svg[..200]
+        );
+        // The second page is translated below the first.
+        assert!(svg.contains("transform=\"matrix(1 0 0 1 0 50)\""));
+    }
+
+    #[test]
+    fn test_direct_compile_skips_assets_for_other_formats() {
+        let quill_yaml = "quill:\n  name: \"print_quill\"\n  version: \"1.0\"\n  backend: \"typst\"\n  description: \"Test\"\nassets:\n  assets/print.txt:\n    formats: [pdf]\n";
+        let mut assets = HashMap::new();
+        assets.insert(
+            "print.txt".to_string(),
+            FileTreeNode::File {
+                contents: b"print only".to_vec(),
+            },
+        );
+        let mut files = HashMap::new();
+        files.insert(
+            "Quill.yaml".to_string(),
+            FileTreeNode::File {
+                contents: quill_yaml.as_bytes().to_vec(),
+            },
+        );
+        files.insert(
+            "assets".to_string(),
+            FileTreeNode::Directory { files: assets },
+        );
+        let source = QuillSource::from_tree(FileTreeNode::Directory { files }).unwrap();
+        let plate = "#read(\"assets/print.txt\")";
+
+        assert!(compile_to_pdf(&source, plate, "{}").is_ok());
+        assert!(matches!(
+            compile_to_txt(&source, plate, "{}"),
+            Err(RenderError::CompilationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_txt_extracts_text_in_order() {
+        let plate = "#set page(width: 200pt, height: auto)\nFirst line\n\nSecond *bold* line\n#rect(width: 10pt)\n#pagebreak()\nNext page";
+        let text = compile_to_txt(&test_source(), plate, "{}").unwrap();
+        assert_eq!(text, "First line\nSecond bold line\n\nNext page");
+
+        let document = compile_to_document(&test_source(), plate, "{}").unwrap();
+        let result =
+            render_document_pages(&document, Some(&[1]), OutputFormat::Txt, None, None).unwrap();
+        assert_eq!(result.artifacts.len(), 1);
+        assert_eq!(result.artifacts[0].bytes, b"Next page");
+    }
+
+    #[test]
+    fn test_svg_artifacts_carry_page_indices() {
+        let plate = "One\n#pagebreak()\nTwo\n#pagebreak()\nThree";
+        let document = compile_to_document(&test_source(), plate, "{}").unwrap();
+
+        let all = render_document_pages(&document, None, OutputFormat::Svg, None, None).unwrap();
+        let pages: Vec<_> = all.artifacts.iter().map(|a| a.page).collect();
+        assert_eq!(pages, vec![Some(0), Some(1), Some(2)]);
+
+        let selected =
+            render_document_pages(&document, Some(&[2, 0]), OutputFormat::Svg, None, None).unwrap();
+        let pages: Vec<_> = selected.artifacts.iter().map(|a| a.page).collect();
+        assert_eq!(pages, vec![Some(2), Some(0)]);
+
+        let pdf = render_document_pages(&document, None, OutputFormat::Pdf, None, None).unwrap();
+        assert_eq!(pdf.artifacts[0].page, None);
+    }
+
+    #[test]
+    fn test_pdf_to_writer_matches_buffered_output() {
+        let plate = "Hello\n#pagebreak()\nWorld";
+        let buffered = compile_to_pdf(&test_source(), plate, "{}").unwrap();
+        let mut written = Vec::new();
+        compile_to_pdf_to_writer(&test_source(), plate, "{}", &mut written).unwrap();
+        assert_eq!(written, buffered);
+    }
+
+    #[test]
+    fn test_pdf_to_writer_reports_write_failure_as_output_error() {
+        struct FullDisk;
+
+        impl Write for FullDisk {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("no space left on device"))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        match compile_to_pdf_to_writer(&test_source(), "Hello", "{}", FullDisk) {
+            Err(RenderError::OutputFailed { diag }) => {
+                assert_eq!(diag.code.as_deref(), Some("typst::pdf_write"));
+                assert!(diag.message.contains("no space left on device"));
+            }
+            other => panic!("expected OutputFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pdf_standard_declares_pdfa_conformance() {
+        let plate = "#set document(date: datetime(year: 2024, month: 1, day: 1))\nArchived";
+        let pdf = compile_to_pdf_with_standard(&test_source(), plate, "{}", Some(PdfStandard::A2b))
+            .unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("pdfaid:part"), "missing PDF/A identification");
+        assert!(text.contains("pdfaid:conformance"));
+
+        let regular = compile_to_pdf(&test_source(), plate, "{}").unwrap();
+        assert!(!String::from_utf8_lossy(&regular).contains("pdfaid:part"));
+
+        match compile_to_pdf_with_standard(&test_source(), "Undated", "{}", Some(PdfStandard::A2b))
+        {
+            Err(RenderError::CompilationFailed { diags }) => {
+                assert_eq!(diags[0].code.as_deref(), Some("typst::pdf_standard"));
+            }
+            other => panic!(
+                "expected CompilationFailed, got {:?}",
+                other.map(|b| b.len())
+            ),
+        }
+    }
+
+    #[test]
+    fn test_pdf_compile_error_returns_diagnostics() {
+        let plate = "#let x = \n#undefined-function()";
+        match compile_to_pdf(&test_source(), plate, "{}") {
+            Err(RenderError::CompilationFailed { diags }) => {
+                assert!(!diags.is_empty());
+                assert!(diags.iter().all(|d| d.severity == Severity::Error));
+            }
+            other => panic!(
+                "expected CompilationFailed, got {:?}",
+                other.map(|b| b.len())
             ),
-        }),
+        }
+    }
+
+    #[test]
+    fn test_compile_error_column_counts_characters() {
+        let plate = "Intro\n😀 é #missing-function()";
+        let diags = match compile_to_pdf(&test_source(), plate, "{}") {
+            Err(RenderError::CompilationFailed { diags }) => diags,
+            other => panic!(
+                "expected CompilationFailed, got {:?}",
+                other.map(|b| b.len())
+            ),
+        };
+        let location = diags[0].location.as_ref().expect("location");
+        assert_eq!((location.line, location.column), (2, 6));
+    }
+
+    #[test]
+    fn test_compile_error_collects_every_diagnostic() {
+        let plate = "#let a = (\nMiddle text\n#let b = ]";
+        let diags = match compile_to_pdf(&test_source(), plate, "{}") {
+            Err(RenderError::CompilationFailed { diags }) => diags,
+            other => panic!(
+                "expected CompilationFailed, got {:?}",
+                other.map(|b| b.len())
+            ),
+        };
+        let lines: Vec<u32> = diags
+            .iter()
+            .filter_map(|d| d.location.as_ref().map(|l| l.line))
+            .collect();
+        assert!(diags.len() >= 2, "expected every error, got {:?}", diags);
+        assert!(
+            lines.contains(&1) && lines.contains(&3),
+            "lines: {:?}",
+            lines
+        );
+    }
+
+    #[test]
+    fn test_svg_combined_empty_document() {
+        let document = PagedDocument::default();
+        assert_eq!(
+            svg_combined(&document),
+            br#"<svg xmlns="http://www.w3.org/2000/svg"/>"#.to_vec()
+        );
     }
 }
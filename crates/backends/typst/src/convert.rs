@@ -5,8 +5,10 @@
 //! ## Key Functions
 //!
 //! - [`mark_to_typst()`] - Primary conversion function for Markdown to Typst
+//! - [`mark_to_typst_with_options()`] - Conversion with opt-in extensions (math)
 //! - [`escape_markup()`] - Escapes text for safe use in Typst markup context
 //! - [`escape_string()`] - Escapes text for embedding in Typst string literals
+//! - [`typst_literal()`] - Renders a field value as a Typst code literal
 //!
 //! ## Quick Example
 //!
@@ -30,7 +32,11 @@
 
 use pulldown_cmark::{Event, Parser, Tag, TagEnd};
 use quillmark_core::error::MAX_NESTING_DEPTH;
+use quillmark_core::QuillValue;
+use std::collections::HashSet;
 use std::ops::Range;
+use std::sync::Arc;
+use unicode_normalization::UnicodeNormalization;
 
 /// Errors that can occur during markdown to Typst conversion
 #[derive(Debug, thiserror::Error)]
@@ -45,6 +51,25 @@ pub enum ConversionError {
     },
 }
 
+/// Label name for a heading: the ASCII mode of the helper package's `slug`.
+/// Lowercases, reduces accented letters to their base letter, drops other
+/// characters, and joins words with hyphens.
+fn slug(title: &str) -> String {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in title.to_lowercase().nfd() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words.join("-")
+}
+
 /// Escapes text for safe use in Typst markup context.
 ///
 /// This function escapes all Typst-special characters to prevent:
@@ -91,6 +116,206 @@ pub fn escape_string(s: &str) -> String {
     out
 }
 
+/// Renders a field value as a Typst code literal.
+///
+/// Strings become quoted literals via [`escape_string()`], numbers and
+/// booleans are written natively, null becomes `none`, arrays become Typst
+/// arrays and objects become dictionaries with quoted keys. The result can be
+/// spliced into a plate after `#` or inside a code block.
+///
+/// Values nested deeper than [`MAX_NESTING_DEPTH`] are rejected.
+pub fn typst_literal(value: &QuillValue) -> Result<String, ConversionError> {
+    let depth = value.nesting_depth();
+    if depth > MAX_NESTING_DEPTH {
+        return Err(ConversionError::NestingTooDeep {
+            depth,
+            max: MAX_NESTING_DEPTH,
+        });
+    }
+    let mut out = String::new();
+    push_typst_literal(&mut out, value.as_json());
+    Ok(out)
+}
+
+fn push_typst_literal(out: &mut String, value: &serde_json::Value) {
+    use serde_json::Value;
+    match value {
+        Value::Null => out.push_str("none"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            let text = n.to_string();
+            out.push_str(&text);
+            // Keep floats typed as floats in Typst even if rendered integral.
+            if n.is_f64() && !text.contains(['.', 'e', 'E']) {
+                out.push_str(".0");
+            }
+        }
+        Value::String(s) => {
+            out.push('"');
+            out.push_str(&escape_string(s));
+            out.push('"');
+        }
+        Value::Array(items) => {
+            out.push('(');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                push_typst_literal(out, item);
+            }
+            // A one-element array needs a trailing comma to not read as
+            // a parenthesized expression.
+            if items.len() == 1 {
+                out.push(',');
+            }
+            out.push(')');
+        }
+        Value::Object(map) => {
+            if map.is_empty() {
+                out.push_str("(:)");
+                return;
+            }
+            out.push('(');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push('"');
+                out.push_str(&escape_string(key));
+                out.push_str("\": ");
+                push_typst_literal(out, item);
+            }
+            out.push(')');
+        }
+    }
+}
+
+/// Escapes math content for use between Typst `$` delimiters.
+///
+/// `#` would switch to code mode and `//`/`/*` would start a comment that
+/// swallows the closing delimiter, so both are escaped. A trailing backslash
+/// is padded so it cannot escape the closing `$`.
+fn escape_math(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '#' => out.push_str("\\#"),
+            '$' => out.push_str("\\$"),
+            '/' if matches!(chars.peek(), Some('/') | Some('*')) => out.push_str("\\/"),
+            c => out.push(c),
+        }
+    }
+    if out.ends_with('\\') {
+        out.push(' ');
+    }
+    out
+}
+
+/// How paragraphs are separated in [`mark_to_typst_with_options`] output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParagraphBreak {
+    /// A blank line (`\n\n`).
+    #[default]
+    BlankLine,
+    /// An explicit `#parbreak()` call.
+    Parbreak,
+}
+
+/// Indentation emitted per list nesting level in
+/// [`mark_to_typst_with_options`] output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListIndent {
+    /// The given number of spaces per level (at least one).
+    Spaces(usize),
+    /// One tab per level.
+    Tab,
+}
+
+impl Default for ListIndent {
+    fn default() -> Self {
+        ListIndent::Spaces(2)
+    }
+}
+
+impl ListIndent {
+    /// Indentation for `levels` nesting levels.
+    fn repeat(self, levels: usize) -> String {
+        match self {
+            ListIndent::Spaces(width) => " ".repeat(width.max(1) * levels),
+            ListIndent::Tab => "\t".repeat(levels),
+        }
+    }
+}
+
+/// Maps link destinations before they are emitted, e.g. to resolve relative
+/// paths against a base URL. See [`MarkOptions::link_rewriter`].
+///
+/// Two rewriters compare equal only when they share the same closure.
+#[derive(Clone)]
+pub struct LinkRewriter(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl LinkRewriter {
+    /// Wrap a closure mapping a link destination to its replacement.
+    pub fn new(rewrite: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(rewrite))
+    }
+
+    /// Rewrite one link destination.
+    pub fn rewrite(&self, url: &str) -> String {
+        (self.0)(url)
+    }
+}
+
+impl std::fmt::Debug for LinkRewriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LinkRewriter(..)")
+    }
+}
+
+impl PartialEq for LinkRewriter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for LinkRewriter {}
+
+/// Options for [`mark_to_typst_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkOptions {
+    /// Parse `$x$` (inline) and `$$x$$` (display) as math. Off by default so
+    /// `$` stays a literal character.
+    pub math: bool,
+    /// Emit parsed math as Typst math. When off, math is emitted as raw text,
+    /// since LaTeX-style input is often not valid Typst math.
+    pub math_passthrough: bool,
+    /// Separator emitted after each top-level paragraph.
+    pub paragraph_break: ParagraphBreak,
+    /// Drop the separator (and trailing newlines) after the final block, for
+    /// output interpolated mid-template.
+    pub trim_trailing: bool,
+    /// Indentation per list nesting level (two spaces by default).
+    pub list_indent: ListIndent,
+    /// Applied to every link destination before escaping. Absolute URLs are
+    /// passed to it too; return them unchanged to keep them. Image paths are
+    /// not rewritten.
+    pub link_rewriter: Option<LinkRewriter>,
+    /// Append a label to each heading (`= Introduction <introduction>`) so
+    /// plates can `@reference` it. Labels follow the helper's `slug`; a
+    /// repeated slug gets `-1`, `-2`, ... appended.
+    pub heading_labels: bool,
+    /// Emit `#pagebreak()` for a top-level block consisting of exactly this
+    /// text on its own (e.g. [`DEFAULT_PAGE_BREAK_MARKER`]). The marker
+    /// inside code blocks, lists, or running text is left alone. `None`
+    /// disables page breaks.
+    pub page_break_marker: Option<String>,
+}
+
+/// The conventional page-break marker, `<!-- pagebreak -->`. As an HTML
+/// comment it renders as nothing in other Markdown viewers.
+pub const DEFAULT_PAGE_BREAK_MARKER: &str = "<!-- pagebreak -->";
+
 #[derive(Debug, Clone)]
 enum ListType {
     Bullet,
@@ -167,8 +392,32 @@ fn longest_backtick_run(s: &str) -> usize {
     max_run
 }
 
+/// Pushes `text` as inline raw text, using enough backticks to avoid
+/// delimiter collision.
+fn push_inline_raw(output: &mut String, text: &str) {
+    let max_run = longest_backtick_run(text);
+    let delim_len = max_run + 1;
+    let delim: String = "`".repeat(delim_len);
+    output.push_str(&delim);
+    // When using multi-backtick delimiters, Typst requires spaces
+    // to separate the delimiters from the content
+    if delim_len > 1 {
+        output.push(' ');
+    }
+    output.push_str(text);
+    if delim_len > 1 {
+        output.push(' ');
+    }
+    output.push_str(&delim);
+}
+
 /// Converts an iterator of markdown events to Typst markup
-fn push_typst<'a, I>(output: &mut String, source: &str, iter: I) -> Result<(), ConversionError>
+fn push_typst<'a, I>(
+    output: &mut String,
+    source: &str,
+    iter: I,
+    options: &MarkOptions,
+) -> Result<(), ConversionError>
 where
     I: Iterator<Item = (Event<'a>, Range<usize>)>,
 {
@@ -181,9 +430,19 @@ where
     let mut table_alignments: Vec<pulldown_cmark::Alignment> = Vec::new(); // Column alignments for current table
     let mut depth = 0; // Track nesting depth for DoS prevention
     let mut in_image = false; // Suppress text events inside ![alt](src)
+    let mut heading_text: Option<String> = None; // Plain text of the current heading, for its label
+    let mut heading_slugs: HashSet<String> = HashSet::new();
+    let mut in_page_break = false; // Skip the events of a page-break marker block
     let iter = iter.peekable();
 
     for (event, range) in iter {
+        if in_page_break {
+            if matches!(event, Event::End(TagEnd::Paragraph | TagEnd::HtmlBlock)) {
+                in_page_break = false;
+                depth -= 1;
+            }
+            continue;
+        }
         match event {
             Event::Start(tag) => {
                 // Track nesting depth
@@ -195,6 +454,22 @@ where
                     });
                 }
 
+                let is_page_break = depth == 1
+                    && matches!(tag, Tag::Paragraph | Tag::HtmlBlock)
+                    && options
+                        .page_break_marker
+                        .as_deref()
+                        .is_some_and(|marker| source[range.clone()].trim() == marker);
+                if is_page_break {
+                    if !end_newline {
+                        output.push('\n');
+                    }
+                    output.push_str("#pagebreak()\n\n");
+                    end_newline = true;
+                    in_page_break = true;
+                    continue;
+                }
+
                 match tag {
                     Tag::Paragraph => {
                         if !in_list_item {
@@ -205,7 +480,7 @@ where
                             }
                         } else if !list_item_first_block {
                             // Continuation paragraph in list item: blank line + indent
-                            let cont_indent = "  ".repeat(list_stack.len());
+                            let cont_indent = options.list_indent.repeat(list_stack.len());
                             if !end_newline {
                                 output.push('\n');
                             }
@@ -218,7 +493,7 @@ where
                         in_code_block = true;
                         if in_list_item {
                             // Code block inside list item: continuation indent
-                            let cont_indent = "  ".repeat(list_stack.len());
+                            let cont_indent = options.list_indent.repeat(list_stack.len());
                             if !list_item_first_block {
                                 if !end_newline {
                                     output.push('\n');
@@ -263,7 +538,9 @@ where
                         in_list_item = true;
                         list_item_first_block = true;
                         if let Some(list_type) = list_stack.last() {
-                            let indent = "  ".repeat(list_stack.len().saturating_sub(1));
+                            let indent = options
+                                .list_indent
+                                .repeat(list_stack.len().saturating_sub(1));
 
                             match list_type {
                                 ListType::Bullet => {
@@ -309,7 +586,12 @@ where
                         dest_url, title: _, ..
                     } => {
                         output.push_str("#link(\"");
-                        output.push_str(&escape_string(&dest_url));
+                        match &options.link_rewriter {
+                            Some(rewriter) => {
+                                output.push_str(&escape_string(&rewriter.rewrite(&dest_url)))
+                            }
+                            None => output.push_str(&escape_string(&dest_url)),
+                        }
                         output.push_str("\")[");
                         end_newline = false;
                     }
@@ -331,6 +613,9 @@ where
                         let equals = "=".repeat(level as usize);
                         output.push_str(&equals);
                         output.push(' ');
+                        if options.heading_labels {
+                            heading_text = Some(String::new());
+                        }
                         end_newline = false;
                     }
                     Tag::Table(alignments) => {
@@ -380,8 +665,13 @@ where
                 match tag {
                     TagEnd::Paragraph => {
                         if !in_list_item {
-                            output.push('\n');
-                            output.push('\n'); // Extra newline for paragraph separation
+                            match options.paragraph_break {
+                                ParagraphBreak::BlankLine => {
+                                    output.push('\n');
+                                    output.push('\n'); // Extra newline for paragraph separation
+                                }
+                                ParagraphBreak::Parbreak => output.push_str("\n#parbreak()\n"),
+                            }
                             end_newline = true;
                         } else {
                             // End of a block within a list item
@@ -398,7 +688,7 @@ where
                             output.push('\n');
                         }
                         if in_list_item {
-                            let cont_indent = "  ".repeat(list_stack.len());
+                            let cont_indent = options.list_indent.repeat(list_stack.len());
                             output.push_str(&cont_indent);
                         }
                         output.push_str("```\n");
@@ -455,6 +745,21 @@ where
                         in_image = false;
                     }
                     TagEnd::Heading(_) => {
+                        if let Some(text) = heading_text.take() {
+                            let base = slug(&text);
+                            if !base.is_empty() {
+                                let mut label = base.clone();
+                                let mut n = 0;
+                                while heading_slugs.contains(&label) {
+                                    n += 1;
+                                    label = format!("{}-{}", base, n);
+                                }
+                                output.push_str(" <");
+                                output.push_str(&label);
+                                output.push('>');
+                                heading_slugs.insert(label);
+                            }
+                        }
                         output.push('\n');
                         output.push('\n'); // Extra newline after heading
                         end_newline = true;
@@ -482,6 +787,9 @@ where
                 }
             }
             Event::Text(text) => {
+                if let Some(heading) = heading_text.as_mut() {
+                    heading.push_str(&text);
+                }
                 if in_image {
                     // Suppress alt text inside ![alt](src) — spec §6.3
                 } else if in_code_block {
@@ -495,21 +803,33 @@ where
                 }
             }
             Event::Code(text) => {
-                // Inline code: use enough backticks to avoid delimiter collision
-                let max_run = longest_backtick_run(&text);
-                let delim_len = max_run + 1;
-                let delim: String = std::iter::repeat('`').take(delim_len).collect();
-                output.push_str(&delim);
-                // When using multi-backtick delimiters, Typst requires spaces
-                // to separate the delimiters from the content
-                if delim_len > 1 {
-                    output.push(' ');
+                if let Some(heading) = heading_text.as_mut() {
+                    heading.push_str(&text);
+                }
+                push_inline_raw(output, &text);
+                end_newline = false;
+            }
+            Event::InlineMath(text) => {
+                if options.math_passthrough {
+                    output.push('$');
+                    output.push_str(&escape_math(&text));
+                    output.push('$');
+                } else {
+                    push_inline_raw(output, &text);
                 }
-                output.push_str(&text);
-                if delim_len > 1 {
-                    output.push(' ');
+                end_newline = false;
+            }
+            Event::DisplayMath(text) => {
+                if options.math_passthrough {
+                    // Surrounding spaces make this Typst block math
+                    output.push_str("$ ");
+                    output.push_str(escape_math(text.trim()).trim_end());
+                    output.push_str(" $");
+                } else {
+                    output.push_str("#raw(block: true, \"");
+                    output.push_str(&escape_string(text.trim()));
+                    output.push_str("\")");
                 }
-                output.push_str(&delim);
                 end_newline = false;
             }
             Event::HardBreak => {
@@ -825,16 +1145,40 @@ where
         }
     }
 }
+/// Converts CommonMark markdown to Typst markup.
+///
+/// Equivalent to [`mark_to_typst_with_options`] with default options (no math).
 pub fn mark_to_typst(markdown: &str) -> Result<String, ConversionError> {
+    mark_to_typst_with_options(markdown, &MarkOptions::default())
+}
+
+/// Converts CommonMark markdown to Typst markup with the given [`MarkOptions`].
+pub fn mark_to_typst_with_options(
+    markdown: &str,
+    mark_options: &MarkOptions,
+) -> Result<String, ConversionError> {
     let mut options = pulldown_cmark::Options::empty();
     options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
     options.insert(pulldown_cmark::Options::ENABLE_TABLES);
+    if mark_options.math {
+        options.insert(pulldown_cmark::Options::ENABLE_MATH);
+    }
 
     let parser = Parser::new_ext(markdown, options);
     let fixer = MarkdownFixer::new(parser.into_offset_iter(), markdown);
     let mut typst_output = String::new();
 
-    push_typst(&mut typst_output, markdown, fixer)?;
+    push_typst(&mut typst_output, markdown, fixer, mark_options)?;
+
+    if mark_options.trim_trailing {
+        let mut end = typst_output.trim_end_matches('\n').len();
+        if mark_options.paragraph_break == ParagraphBreak::Parbreak {
+            if let Some(rest) = typst_output[..end].strip_suffix("\n#parbreak()") {
+                end = rest.len();
+            }
+        }
+        typst_output.truncate(end);
+    }
 
     Ok(typst_output)
 }
@@ -918,6 +1262,55 @@ mod tests {
         assert_eq!(escape_string("\x01"), "\\u{1}");
     }
 
+    fn literal(value: serde_json::Value) -> String {
+        typst_literal(&QuillValue::from_json(value)).unwrap()
+    }
+
+    #[test]
+    fn test_typst_literal_scalars() {
+        assert_eq!(literal(serde_json::json!(null)), "none");
+        assert_eq!(literal(serde_json::json!(true)), "true");
+        assert_eq!(literal(serde_json::json!(false)), "false");
+        assert_eq!(literal(serde_json::json!(42)), "42");
+        assert_eq!(literal(serde_json::json!(-7)), "-7");
+        assert_eq!(literal(serde_json::json!(1.5)), "1.5");
+        assert_eq!(literal(serde_json::json!(2.0)), "2.0");
+        assert_eq!(
+            literal(serde_json::json!("say \"hi\"\n")),
+            "\"say \\\"hi\\\"\\n\""
+        );
+    }
+
+    #[test]
+    fn test_typst_literal_collections() {
+        assert_eq!(literal(serde_json::json!([])), "()");
+        assert_eq!(literal(serde_json::json!(["a"])), "(\"a\",)");
+        assert_eq!(literal(serde_json::json!({})), "(:)");
+        assert_eq!(
+            literal(serde_json::json!({
+                "name": "Ada",
+                "tags": ["x", 1, null],
+                "meta": {"ok": true, "nested key": [2.5]}
+            })),
+            "(\"name\": \"Ada\", \"tags\": (\"x\", 1, none), \"meta\": (\"ok\": true, \"nested key\": (2.5,)))"
+        );
+    }
+
+    #[test]
+    fn test_typst_literal_depth_guard() {
+        let mut deep = serde_json::json!(1);
+        for _ in 0..1000 {
+            deep = serde_json::json!([deep]);
+        }
+        match typst_literal(&QuillValue::from_json(deep)) {
+            Err(ConversionError::NestingTooDeep { depth, max }) => {
+                assert_eq!(depth, 1000);
+                assert_eq!(max, MAX_NESTING_DEPTH);
+            }
+            other => panic!("expected NestingTooDeep, got {:?}", other.map(|s| s.len())),
+        }
+    }
+
     // Tests for mark_to_typst - Basic Text Formatting
     #[test]
     fn test_basic_text_formatting() {
@@ -984,6 +1377,50 @@ mod tests {
         assert_eq!(typst, "- Item 1\n- Item 2\n  - Nested item\n- Item 3\n\n");
     }
 
+    #[test]
+    fn test_list_indent_option() {
+        let markdown = "- Level 1\n  - Level 2\n    - Level 3\n\n      More.\n- Back";
+        assert_eq!(
+            mark_to_typst_with_options(markdown, &MarkOptions::default()).unwrap(),
+            "- Level 1\n  - Level 2\n    - Level 3\n\n      More.\n- Back\n\n"
+        );
+
+        let four = MarkOptions {
+            list_indent: ListIndent::Spaces(4),
+            ..MarkOptions::default()
+        };
+        assert_eq!(
+            mark_to_typst_with_options(markdown, &four).unwrap(),
+            "- Level 1\n    - Level 2\n        - Level 3\n\n            More.\n- Back\n\n"
+        );
+
+        let tab = MarkOptions {
+            list_indent: ListIndent::Tab,
+            ..MarkOptions::default()
+        };
+        assert_eq!(
+            mark_to_typst_with_options("1. One\n   1. Two", &tab).unwrap(),
+            "+ One\n\t+ Two\n\n"
+        );
+    }
+
+    #[test]
+    fn test_list_indent_still_limits_depth() {
+        let mut markdown = String::new();
+        for i in 0..=MAX_NESTING_DEPTH {
+            markdown.push_str(&"  ".repeat(i));
+            markdown.push_str("- item\n");
+        }
+        let options = MarkOptions {
+            list_indent: ListIndent::Spaces(4),
+            ..MarkOptions::default()
+        };
+        assert!(matches!(
+            mark_to_typst_with_options(&markdown, &options),
+            Err(ConversionError::NestingTooDeep { .. })
+        ));
+    }
+
     #[test]
     fn test_deeply_nested_list() {
         let markdown = "- Level 1\n  - Level 2\n    - Level 3";
@@ -1000,6 +1437,67 @@ mod tests {
         assert_eq!(typst, "#link(\"https://example.com\")[Link text]\n\n");
     }
 
+    #[test]
+    fn test_link_rewriter_prefixes_base_url() {
+        let options = MarkOptions {
+            link_rewriter: Some(LinkRewriter::new(|url| {
+                if url.contains("://") {
+                    url.to_string()
+                } else {
+                    format!("https://docs.example.com/{}", url.trim_start_matches("../"))
+                }
+            })),
+            ..MarkOptions::default()
+        };
+        let markdown = "[guide](../guide.md) and [home](https://example.com) ![logo](logo.png)";
+        assert_eq!(
+            mark_to_typst_with_options(markdown, &options).unwrap(),
+            "#link(\"https://docs.example.com/guide.md\")[guide] and #link(\"https://example.com\")[home] #image(\"logo.png\")\n\n"
+        );
+    }
+
+    #[test]
+    fn test_link_rewriter_maps_md_links_to_labels() {
+        let options = MarkOptions {
+            link_rewriter: Some(LinkRewriter::new(|url| match url.strip_suffix(".md") {
+                Some(page) => format!("#{}", page),
+                None => url.to_string(),
+            })),
+            ..MarkOptions::default()
+        };
+        assert_eq!(
+            mark_to_typst_with_options("See [setup](setup.md).", &options).unwrap(),
+            "See #link(\"#setup\")[setup].\n\n"
+        );
+    }
+
+    #[test]
+    fn test_reference_link_resolves_definition() {
+        let markdown = "Read [the docs][docs] first.\n\n[docs]: https://example.com/docs";
+        assert_eq!(
+            mark_to_typst(markdown).unwrap(),
+            "Read #link(\"https://example.com/docs\")[the docs] first.\n\n"
+        );
+    }
+
+    #[test]
+    fn test_shortcut_reference_link_resolves_definition() {
+        let markdown = "See [Example] and [example][].\n\n[example]: https://example.com";
+        assert_eq!(
+            mark_to_typst(markdown).unwrap(),
+            "See #link(\"https://example.com\")[Example] and #link(\"https://example.com\")[example].\n\n"
+        );
+    }
+
+    #[test]
+    fn test_undefined_reference_link_stays_literal() {
+        let markdown = "See [text][missing] and [alone].";
+        assert_eq!(
+            mark_to_typst(markdown).unwrap(),
+            "See \\[text\\]\\[missing\\] and \\[alone\\].\n\n"
+        );
+    }
+
     #[test]
     fn test_link_in_sentence() {
         let markdown = "Visit [our site](https://example.com) for more.";
@@ -1401,6 +1899,81 @@ mod tests {
         assert_eq!(typst, "= First\n\n== Second\n\n=== Third\n\n");
     }
 
+    #[test]
+    fn test_heading_labels_from_titles() {
+        let options = MarkOptions {
+            heading_labels: true,
+            ..MarkOptions::default()
+        };
+        let markdown = "# Getting Started\n\n## Crème **Brûlée**, `v2`!";
+        assert_eq!(
+            mark_to_typst_with_options(markdown, &options).unwrap(),
+            "= Getting Started <getting-started>\n\n== Crème #strong[Brûlée], `v2`! <creme-brulee-v2>\n\n"
+        );
+        assert_eq!(
+            mark_to_typst("# Getting Started").unwrap(),
+            "= Getting Started\n\n"
+        );
+    }
+
+    #[test]
+    fn test_heading_labels_disambiguate_duplicates() {
+        let options = MarkOptions {
+            heading_labels: true,
+            ..MarkOptions::default()
+        };
+        let markdown = "# Notes\n\n## Notes\n\n## Notes\n\n## ???";
+        assert_eq!(
+            mark_to_typst_with_options(markdown, &options).unwrap(),
+            "= Notes <notes>\n\n== Notes <notes-1>\n\n== Notes <notes-2>\n\n== ???\n\n"
+        );
+    }
+
+    #[test]
+    fn test_page_break_marker() {
+        let options = MarkOptions {
+            page_break_marker: Some(DEFAULT_PAGE_BREAK_MARKER.to_string()),
+            ..MarkOptions::default()
+        };
+        assert_eq!(
+            mark_to_typst_with_options("One\n\n<!-- pagebreak -->\n\nTwo", &options).unwrap(),
+            "One\n\n#pagebreak()\n\nTwo\n\n"
+        );
+        // Off by default: the comment is dropped like any other HTML.
+        assert_eq!(
+            mark_to_typst("One\n\n<!-- pagebreak -->\n\nTwo").unwrap(),
+            "One\n\nTwo\n\n"
+        );
+
+        let custom = MarkOptions {
+            page_break_marker: Some("\\pagebreak".to_string()),
+            ..MarkOptions::default()
+        };
+        assert_eq!(
+            mark_to_typst_with_options("One\n\n\\pagebreak\n\nTwo", &custom).unwrap(),
+            "One\n\n#pagebreak()\n\nTwo\n\n"
+        );
+    }
+
+    #[test]
+    fn test_page_break_marker_ignored_outside_own_block() {
+        let options = MarkOptions {
+            page_break_marker: Some("\\pagebreak".to_string()),
+            ..MarkOptions::default()
+        };
+        for markdown in [
+            "```\n\\pagebreak\n```",
+            "    \\pagebreak",
+            "Text then \\pagebreak inline",
+            "- \\pagebreak",
+            "> \\pagebreak",
+        ] {
+            let out = mark_to_typst_with_options(markdown, &options).unwrap();
+            assert!(!out.contains("#pagebreak()"), "{:?} -> {:?}", markdown, out);
+            assert_eq!(out, mark_to_typst(markdown).unwrap());
+        }
+    }
+
     #[test]
     fn test_heading_followed_by_paragraph() {
         let markdown = "# Heading\n\nThis is a paragraph.";
@@ -1963,6 +2536,102 @@ mod tests {
         let out = mark_to_typst(md).unwrap();
         assert!(out.contains("[], [], [],"), "multiple empty cells: {out}");
     }
+
+    // Tests for math conversion
+
+    fn math_options(passthrough: bool) -> MarkOptions {
+        MarkOptions {
+            math: true,
+            math_passthrough: passthrough,
+            ..MarkOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_math_disabled_by_default() {
+        assert_eq!(mark_to_typst("$x^2$").unwrap(), "\\$x^2\\$\n\n");
+    }
+
+    #[test]
+    fn test_inline_math_passthrough() {
+        let result = mark_to_typst_with_options("Area is $pi r^2$.", &math_options(true)).unwrap();
+        assert_eq!(result, "Area is $pi r^2$.\n\n");
+    }
+
+    #[test]
+    fn test_inline_math_raw_without_passthrough() {
+        let result =
+            mark_to_typst_with_options("Area is $\\pi r^2$.", &math_options(false)).unwrap();
+        assert_eq!(result, "Area is `\\pi r^2`.\n\n");
+    }
+
+    #[test]
+    fn test_display_math_passthrough() {
+        let result =
+            mark_to_typst_with_options("$$\nsum_(i=1)^n i\n$$", &math_options(true)).unwrap();
+        assert_eq!(result, "$ sum_(i=1)^n i $\n\n");
+    }
+
+    #[test]
+    fn test_display_math_raw_without_passthrough() {
+        let result =
+            mark_to_typst_with_options("$$\\frac{a}{\"b\"}$$", &math_options(false)).unwrap();
+        assert_eq!(result, "#raw(block: true, \"\\\\frac{a}{\\\"b\\\"}\")\n\n");
+    }
+
+    #[test]
+    fn test_math_passthrough_escapes_code_and_comments() {
+        let result =
+            mark_to_typst_with_options("$a #read(\"x\") // b$", &math_options(true)).unwrap();
+        assert_eq!(result, "$a \\#read(\"x\") \\// b$\n\n");
+
+        assert_eq!(escape_math("a\\"), "a\\ ");
+    }
+
+    // Tests for paragraph separation options
+
+    #[test]
+    fn test_trim_trailing_removes_final_separator() {
+        let options = MarkOptions {
+            trim_trailing: true,
+            ..MarkOptions::default()
+        };
+        let result = mark_to_typst_with_options("First.\n\nSecond.", &options).unwrap();
+        assert_eq!(result, "First.\n\nSecond.");
+
+        let result = mark_to_typst_with_options("- a\n- b", &options).unwrap();
+        assert_eq!(result, "- a\n- b");
+    }
+
+    #[test]
+    fn test_parbreak_paragraph_separator() {
+        let options = MarkOptions {
+            paragraph_break: ParagraphBreak::Parbreak,
+            ..MarkOptions::default()
+        };
+        let result =
+            mark_to_typst_with_options("First.\n\n# Heading\n\nSecond.", &options).unwrap();
+        assert_eq!(
+            result,
+            "First.\n#parbreak()\n= Heading\n\nSecond.\n#parbreak()\n"
+        );
+
+        let trimmed = MarkOptions {
+            trim_trailing: true,
+            ..options
+        };
+        let result = mark_to_typst_with_options("First.\n\nSecond.", &trimmed).unwrap();
+        assert_eq!(result, "First.\n#parbreak()\nSecond.");
+    }
+
+    #[test]
+    fn test_default_options_match_mark_to_typst() {
+        let markdown = "Para one.\n\n- item\n\n```\ncode\n```\n\nPara two.";
+        assert_eq!(
+            mark_to_typst_with_options(markdown, &MarkOptions::default()).unwrap(),
+            mark_to_typst(markdown).unwrap()
+        );
+    }
 }
 
 // Additional robustness tests
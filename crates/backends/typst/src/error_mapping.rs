@@ -1,10 +1,14 @@
 //! Error mapping utilities for converting Typst diagnostics to Quillmark diagnostics.
 
 use crate::world::QuillWorld;
+use quillmark_core::error::offset_to_location;
 use quillmark_core::{Diagnostic, Location, Severity};
 use typst::diag::SourceDiagnostic;
 
 /// Converts Typst diagnostics to Quillmark diagnostics.
+///
+/// Every diagnostic is kept. Typst reports a failed compile's errors in one
+/// batch, so there is no fail-fast mode to opt into.
 pub fn map_typst_errors(errors: &[SourceDiagnostic], world: &QuillWorld) -> Vec<Diagnostic> {
     errors
         .iter()
@@ -50,15 +54,11 @@ fn resolve_span_to_location(span: &typst::syntax::Span, world: &QuillWorld) -> O
     let source = world.source(source_id).ok()?;
     let range = source.range(*span)?;
 
-    let text = source.text();
-    let line = text[..range.start].matches('\n').count() + 1;
-    let column = range.start - text[..range.start].rfind('\n').map_or(0, |pos| pos + 1) + 1;
-
-    Some(Location {
-        file: source.id().vpath().as_rootless_path().display().to_string(),
-        line: line as u32,
-        column: column as u32,
-    })
+    Some(offset_to_location(
+        source.id().vpath().as_rootless_path().display().to_string(),
+        source.text(),
+        range.start,
+    ))
 }
 
 #[cfg(test)]
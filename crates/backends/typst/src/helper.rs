@@ -8,6 +8,19 @@
 //! The generated package exports:
 //! - `data` - A dictionary containing all document fields, with markdown fields
 //!   and date fields automatically converted to Typst values
+//! - `seed` - The per-render seed from `RenderOptions::seed`, or `none`
+//! - `base64` - Base64-encodes a string or bytes (or decodes with `decode: true`),
+//!   for inlining small assets
+//! - `slug` - Turns a title (string or content) into a hyphenated label name
+//! - `number` - Formats a number with fixed precision, digit grouping, percent
+//!   mode, and locale-specific separators
+//! - `date` - Parses dates in a custom input format and formats them with
+//!   localized month names
+//! - `truncate` - Shortens text to a character count with an ellipsis,
+//!   optionally at a word boundary
+//! - `join` - Joins list items with a separator and an optional final
+//!   conjunction (`"a, b, and c"`)
+//! - `page-of` - "Page X of Y" text for running headers and footers
 //!
 //! ## Usage in Plates
 //!
@@ -18,6 +31,18 @@
 //! #data.BODY
 //! #data.date
 //! ```
+//!
+//! Running headers and footers read fields from `data` and page numbers from
+//! Typst's page counter:
+//!
+//! ```typst
+//! #import "@local/quillmark-helper:0.1.0": data, page-of
+//!
+//! #set page(
+//!   header: align(right, data.title),
+//!   footer: align(center, page-of()),
+//! )
+//! ```
 
 use crate::convert::escape_string;
 
@@ -122,6 +147,212 @@ mod tests {
         assert!(toml.contains("entrypoint = \"lib.typ\""));
     }
 
+    fn compile_plate(plate: &str) -> Result<(), quillmark_core::RenderError> {
+        compile_plate_document(plate).map(|_| ())
+    }
+
+    fn compile_plate_document(
+        plate: &str,
+    ) -> Result<typst::layout::PagedDocument, quillmark_core::RenderError> {
+        use quillmark_core::{FileTreeNode, QuillSource};
+        use std::collections::HashMap;
+
+        let quill_yaml = "quill:\n  name: helper_test\n  version: \"1.0\"\n  backend: typst\n  description: Test\n";
+        let mut files = HashMap::new();
+        files.insert(
+            "Quill.yaml".to_string(),
+            FileTreeNode::File {
+                contents: quill_yaml.as_bytes().to_vec(),
+            },
+        );
+        files.insert(
+            "assets".to_string(),
+            FileTreeNode::Directory {
+                files: HashMap::from([(
+                    "blob.bin".to_string(),
+                    FileTreeNode::File {
+                        contents: vec![0xff, 0x00, 0xfe],
+                    },
+                )]),
+            },
+        );
+        let source = QuillSource::from_tree(FileTreeNode::Directory { files }).unwrap();
+        crate::compile::compile_to_document(&source, plate, "{}")
+    }
+
+    /// Concatenated text of every glyph run on each page.
+    fn page_texts(document: &typst::layout::PagedDocument) -> Vec<String> {
+        use typst::layout::{Frame, FrameItem};
+
+        fn collect(frame: &Frame, out: &mut String) {
+            for (_, item) in frame.items() {
+                match item {
+                    FrameItem::Group(group) => collect(&group.frame, out),
+                    FrameItem::Text(text) => out.push_str(&text.text),
+                    _ => {}
+                }
+            }
+        }
+
+        document
+            .pages
+            .iter()
+            .map(|page| {
+                let mut out = String::new();
+                collect(&page.frame, &mut out);
+                out
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_base64_encode_and_round_trip() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": base64
+#assert.eq(base64("hi"), "aGk=")
+#assert.eq(base64("Man"), "TWFu")
+#assert.eq(base64(""), "")
+#assert.eq(base64(read("assets/blob.bin", encoding: none)), "/wD+")
+#assert.eq(str(base64(base64("round trip"), decode: true)), "round trip")
+#assert.eq(base64("/wD+", decode: true), bytes((255, 0, 254)))
+#assert.eq(base64("", decode: true), bytes(()))
+"#;
+        if let Err(e) = compile_plate(plate) {
+            panic!("base64 assertions failed: {:?}", e.diagnostics());
+        }
+    }
+
+    #[test]
+    fn test_base64_rejects_invalid_input() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": base64
+#base64("a*b=", decode: true)
+"#;
+        assert!(compile_plate(plate).is_err());
+    }
+
+    #[test]
+    fn test_slug() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": slug
+#assert.eq(slug("Hello, World!  It's  2024"), "hello-world-its-2024")
+#assert.eq(slug("Crème Brûlée — Ñandú"), "creme-brulee-nandu")
+#assert.eq(slug("Crème Brûlée", ascii: false), "crème-brûlée")
+#assert.eq(slug(""), "")
+#assert.eq(slug([Section *One*]), "section-one")
+#let l = label(slug("Intro"))
+"#;
+        if let Err(e) = compile_plate(plate) {
+            panic!("slug assertions failed: {:?}", e.diagnostics());
+        }
+    }
+
+    #[test]
+    fn test_number() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": number
+#assert.eq(number(1234.5, precision: 2, grouping: true), "1,234.50")
+#assert.eq(number(2.675, precision: 1), "2.7")
+#assert.eq(number(0.004, precision: 2), "0.00")
+#assert.eq(number(-1234567, grouping: true), "-1,234,567")
+#assert.eq(number(12.5), "12.5")
+#assert.eq(number(2.5, precision: 0), "3")
+#assert.eq(number(0.125, percent: true, precision: 1), "12.5%")
+#assert.eq(number(1, percent: true), "100%")
+#assert.eq(number(1234.5, precision: 2, grouping: true, locale: "de-DE"), "1.234,50")
+"#;
+        if let Err(e) = compile_plate(plate) {
+            panic!("number assertions failed: {:?}", e.diagnostics());
+        }
+    }
+
+    #[test]
+    fn test_number_rejects_non_numeric() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": number
+#number("12")
+"#;
+        assert!(compile_plate(plate).is_err());
+    }
+
+    #[test]
+    fn test_date() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": date
+#assert.eq(date("07/04/2024", input-format: "%m/%d/%Y"), datetime(year: 2024, month: 7, day: 4))
+#assert.eq(date("2024-03-09T10:00:00"), datetime(year: 2024, month: 3, day: 9))
+#assert.eq(date("2024-03-09", output: "%e %B %Y", locale: "fr"), "9 mars 2024")
+#assert.eq(date("March 9, 2024", input-format: "%B %d, %Y", output: "%d. %B %Y", locale: "de"), "09. März 2024")
+#assert.eq(date(datetime(year: 2024, month: 12, day: 1), output: "%b %d"), "Dec 01")
+"#;
+        if let Err(e) = compile_plate(plate) {
+            panic!("date assertions failed: {:?}", e.diagnostics());
+        }
+    }
+
+    #[test]
+    fn test_date_rejects_invalid_date() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": date
+#date("02/30/2024", input-format: "%m/%d/%Y")
+"#;
+        let err = compile_plate(plate).unwrap_err();
+        assert!(
+            err.diagnostics()
+                .iter()
+                .any(|d| d.message.contains("invalid date") && d.message.contains("02/30/2024")),
+            "{:?}",
+            err.diagnostics()
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": truncate
+#assert.eq(truncate("Hello, world", 8), "Hello,…")
+#assert.eq(truncate("Hello, world", 20), "Hello, world")
+#assert.eq(truncate("The quick brown fox", 14, word-boundary: true), "The quick…")
+#assert.eq(truncate("The quick brown fox", 10, suffix: "..."), "The qui...")
+#assert.eq(truncate("Crème brûlée 🎉🎉", 14), "Crème brûlée…")
+#assert.eq(truncate("héé🎉🎉🎉", 5, suffix: ""), "héé🎉🎉")
+#assert.eq(truncate([A *bold* summary], 8), "A bold…")
+"#;
+        if let Err(e) = compile_plate(plate) {
+            panic!("truncate assertions failed: {:?}", e.diagnostics());
+        }
+    }
+
+    #[test]
+    fn test_join() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": join
+#assert.eq(join(("a", "b", "c"), last: ", and "), "a, b, and c")
+#assert.eq(join(("a", "b"), last: " and "), "a and b")
+#assert.eq(join(("a", "b", "c")), "a, b, c")
+#assert.eq(join(("a", "b", "c"), sep: "; ", last: "; or "), "a; b; or c")
+#assert.eq(join(()), "")
+#assert.eq(join(("only",), last: ", and "), "only")
+#assert.eq(join((1, 2.5, true, none, [*bold*])), "1, 2.5, true, null, bold")
+"#;
+        if let Err(e) = compile_plate(plate) {
+            panic!("join assertions failed: {:?}", e.diagnostics());
+        }
+    }
+
+    #[test]
+    fn test_page_of() {
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": page-of
+#set page(footer: page-of())
+First
+#pagebreak()
+Second
+"#;
+        let document = compile_plate_document(plate).expect("page-of plate failed");
+        let texts = page_texts(&document);
+        assert_eq!(texts.len(), 2);
+        assert!(texts[0].contains("Page 1 of 2"), "{:?}", texts);
+        assert!(texts[1].contains("Page 2 of 2"), "{:?}", texts);
+
+        let plate = r#"#import "@local/quillmark-helper:0.1.0": page-of
+#set page(header: page-of(template: "{page}/{total}"))
+Only
+"#;
+        let texts = page_texts(&compile_plate_document(plate).unwrap());
+        assert!(texts[0].contains("1/1"), "{:?}", texts);
+    }
+
     #[test]
     fn test_helper_constants() {
         assert_eq!(HELPER_VERSION, "0.1.0");
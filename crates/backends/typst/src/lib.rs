@@ -25,6 +25,7 @@
 //! Note: The `error_mapping` module provides internal utilities for converting Typst
 //! diagnostics to Quillmark diagnostics and is not part of the public API.
 
+mod assets;
 pub mod compile;
 pub mod convert;
 mod error_mapping;
@@ -39,10 +40,12 @@ pub mod fuzz_utils {
     pub use super::helper::inject_json;
 }
 
-use convert::mark_to_typst;
+use convert::{mark_to_typst_with_options, ListIndent, MarkOptions, DEFAULT_PAGE_BREAK_MARKER};
 use quillmark_core::{
-    quill::build_transform_schema, session::SessionHandle, Backend, Diagnostic, OutputFormat,
-    QuillSource, QuillValue, RenderError, RenderOptions, RenderResult, RenderSession, Severity,
+    quill::{build_transform_schema, QuillConfig},
+    session::SessionHandle,
+    Backend, Diagnostic, OutputFormat, QuillSource, QuillValue, RenderError, RenderOptions,
+    RenderResult, RenderSession, Severity,
 };
 use std::any::Any;
 use std::collections::HashMap;
@@ -51,8 +54,12 @@ use std::collections::HashMap;
 #[derive(Debug)]
 pub struct TypstBackend;
 
-const SUPPORTED_FORMATS: &[OutputFormat] =
-    &[OutputFormat::Pdf, OutputFormat::Svg, OutputFormat::Png];
+const SUPPORTED_FORMATS: &[OutputFormat] = &[
+    OutputFormat::Pdf,
+    OutputFormat::Svg,
+    OutputFormat::Png,
+    OutputFormat::Txt,
+];
 
 /// Typst-specific render session.
 ///
@@ -64,6 +71,7 @@ const SUPPORTED_FORMATS: &[OutputFormat] =
 pub struct TypstSession {
     document: typst::layout::PagedDocument,
     page_count: usize,
+    missing_fonts: Vec<String>,
 }
 
 impl TypstSession {
@@ -103,6 +111,16 @@ impl SessionHandle for TypstSession {
     fn render(&self, opts: &RenderOptions) -> Result<RenderResult, RenderError> {
         let format = opts.output_format.unwrap_or(OutputFormat::Pdf);
 
+        if opts.strict_fonts && !self.missing_fonts.is_empty() {
+            return Err(RenderError::CompilationFailed {
+                diags: self
+                    .missing_fonts
+                    .iter()
+                    .map(|family| missing_font_diagnostic(family, Severity::Error))
+                    .collect(),
+            });
+        }
+
         if !SUPPORTED_FORMATS.contains(&format) {
             return Err(RenderError::FormatNotSupported {
                 diag: Box::new(
@@ -116,7 +134,13 @@ impl SessionHandle for TypstSession {
             });
         }
 
-        compile::render_document_pages(&self.document, opts.pages.as_deref(), format, opts.ppi)
+        compile::render_document_pages(
+            &self.document,
+            opts.pages.as_deref(),
+            format,
+            opts.ppi,
+            opts.pdf_standard,
+        )
     }
 
     fn page_count(&self) -> usize {
@@ -126,6 +150,31 @@ impl SessionHandle for TypstSession {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn append(&mut self, other: &dyn SessionHandle) -> Result<(), RenderError> {
+        let Some(other) = other.as_any().downcast_ref::<TypstSession>() else {
+            return Err(RenderError::UnsupportedBackend {
+                diag: Box::new(
+                    Diagnostic::new(
+                        Severity::Error,
+                        "Cannot combine a typst session with a session from another backend"
+                            .to_string(),
+                    )
+                    .with_code("session::backend_mismatch".to_string()),
+                ),
+            });
+        };
+        self.document
+            .pages
+            .extend(other.document.pages.iter().cloned());
+        self.page_count = self.document.pages.len();
+        for family in &other.missing_fonts {
+            if !self.missing_fonts.contains(family) {
+                self.missing_fonts.push(family.clone());
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Borrow the [`TypstSession`] underlying a [`RenderSession`], if the session
@@ -138,20 +187,15 @@ pub fn typst_session_of(session: &RenderSession) -> Option<&TypstSession> {
     session.handle().as_any().downcast_ref::<TypstSession>()
 }
 
-impl Backend for TypstBackend {
-    fn id(&self) -> &'static str {
-        "typst"
-    }
-
-    fn supported_formats(&self) -> &'static [OutputFormat] {
-        SUPPORTED_FORMATS
-    }
-
-    fn open(
+impl TypstBackend {
+    /// Transform the markdown fields of `json_data` and compile the plate,
+    /// loading only the assets `format` needs when it is known.
+    fn open_session(
         &self,
         plate_content: &str,
         source: &QuillSource,
         json_data: &serde_json::Value,
+        format: Option<OutputFormat>,
     ) -> Result<RenderSession, RenderError> {
         let fields = json_data.as_object().map_or_else(HashMap::new, |obj| {
             obj.iter()
@@ -159,8 +203,11 @@ impl Backend for TypstBackend {
                 .collect::<HashMap<_, _>>()
         });
 
-        let transformed_fields =
-            transform_markdown_fields(&fields, &build_transform_schema(source.config()));
+        let transformed_fields = transform_markdown_fields(
+            &fields,
+            &build_transform_schema(source.config()),
+            &mark_options(source.config()),
+        );
         let transformed_json = serde_json::Value::Object(
             transformed_fields
                 .into_iter()
@@ -170,13 +217,79 @@ impl Backend for TypstBackend {
 
         let json_str =
             serde_json::to_string(&transformed_json).unwrap_or_else(|_| "{}".to_string());
-        let document = compile::compile_to_document(source, plate_content, &json_str)?;
+        log::info!("Typst backend compiling for quill: {}", source.name());
+        let (document, missing_fonts) =
+            compile::compile_to_document_for_format(source, plate_content, &json_str, format)?;
         let page_count = document.pages.len();
+        let warnings = missing_fonts
+            .iter()
+            .map(|family| missing_font_diagnostic(family, Severity::Warning))
+            .collect();
         let session = TypstSession {
             document,
             page_count,
+            missing_fonts,
         };
-        Ok(RenderSession::new(Box::new(session)))
+        Ok(RenderSession::new(Box::new(session)).with_warnings(warnings))
+    }
+}
+
+impl Backend for TypstBackend {
+    fn id(&self) -> &'static str {
+        "typst"
+    }
+
+    fn supported_formats(&self) -> &'static [OutputFormat] {
+        SUPPORTED_FORMATS
+    }
+
+    fn open(
+        &self,
+        plate_content: &str,
+        source: &QuillSource,
+        json_data: &serde_json::Value,
+    ) -> Result<RenderSession, RenderError> {
+        self.open_session(plate_content, source, json_data, None)
+    }
+
+    fn open_for_format(
+        &self,
+        plate_content: &str,
+        source: &QuillSource,
+        json_data: &serde_json::Value,
+        format: OutputFormat,
+    ) -> Result<RenderSession, RenderError> {
+        self.open_session(plate_content, source, json_data, Some(format))
+    }
+
+    fn referenced_assets(&self, plate_content: &str) -> Vec<String> {
+        assets::referenced_assets(plate_content)
+    }
+
+    fn validate_quill(&self, source: &QuillSource) -> Result<(), RenderError> {
+        let config = source.config();
+        let plates = [
+            ("plate_file", &config.plate_file),
+            ("fragment_plate_file", &config.fragment_plate_file),
+        ];
+        for (key, file) in plates {
+            if let Some(file) = file.as_deref().filter(|f| !f.ends_with(".typ")) {
+                return Err(RenderError::QuillConfig {
+                    diag: Box::new(
+                        Diagnostic::new(
+                            Severity::Error,
+                            format!("Typst plate '{}' must be a .typ file", file),
+                        )
+                        .with_code("typst::invalid_plate_file".to_string())
+                        .with_hint(format!(
+                            "Point `{}` in Quill.yaml at a Typst source file",
+                            key
+                        )),
+                    ),
+                });
+            }
+        }
+        Ok(())
     }
 }
 
@@ -187,6 +300,63 @@ impl Default for TypstBackend {
     }
 }
 
+/// Diagnostic for a font family the plate requested but the quill lacks.
+fn missing_font_diagnostic(family: &str, severity: Severity) -> Diagnostic {
+    let message = match severity {
+        Severity::Error => format!("Font family '{}' is not available", family),
+        _ => format!(
+            "Font family '{}' is not available; using a fallback font",
+            family
+        ),
+    };
+    Diagnostic::new(severity, message)
+        .with_code("typst::missing_font".to_string())
+        .with_hint(format!(
+            "Add the '{}' font files to the quill's fonts directory",
+            family
+        ))
+}
+
+/// Markdown conversion options from the quill's `typst:` section
+/// (`math`, `math_passthrough`, `list_indent`, `heading_labels`).
+fn mark_options(config: &QuillConfig) -> MarkOptions {
+    let flag = |key: &str| {
+        config
+            .backend_config
+            .get(key)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    };
+    // `list_indent`: a space count, or `tab`.
+    let list_indent = match config.backend_config.get("list_indent") {
+        Some(v) if v.as_str() == Some("tab") => ListIndent::Tab,
+        Some(v) => v
+            .as_i64()
+            .filter(|n| *n > 0)
+            .map(|n| ListIndent::Spaces(n as usize))
+            .unwrap_or_default(),
+        None => ListIndent::default(),
+    };
+    // `page_break`: `true` for the default marker, or a custom marker.
+    let page_break_marker = match config.backend_config.get("page_break") {
+        Some(v) if v.as_bool() == Some(true) => Some(DEFAULT_PAGE_BREAK_MARKER.to_string()),
+        Some(v) => v
+            .as_str()
+            .map(str::trim)
+            .filter(|marker| !marker.is_empty())
+            .map(str::to_string),
+        None => None,
+    };
+    MarkOptions {
+        math: flag("math"),
+        math_passthrough: flag("math_passthrough"),
+        list_indent,
+        heading_labels: flag("heading_labels"),
+        page_break_marker,
+        ..MarkOptions::default()
+    }
+}
+
 /// Check if a field schema indicates markdown content.
 ///
 /// A field is considered markdown if it has:
@@ -223,7 +393,7 @@ fn is_date_field(field_schema: &serde_json::Value) -> bool {
 /// Transform markdown fields to Typst markup based on schema.
 ///
 /// Identifies fields with `contentMediaType = "text/markdown"` and converts
-/// their content using `mark_to_typst()`. This includes recursive handling
+/// their content using `mark_to_typst_with_options()`. This includes recursive handling
 /// of CARDS arrays.
 ///
 /// Also injects a `__meta__` key into the result containing the names of
@@ -232,6 +402,7 @@ fn is_date_field(field_schema: &serde_json::Value) -> bool {
 fn transform_markdown_fields(
     fields: &HashMap<String, QuillValue>,
     schema: &QuillValue,
+    options: &MarkOptions,
 ) -> HashMap<String, QuillValue> {
     let mut result = fields.clone();
     let schema_json = schema.as_json();
@@ -248,7 +419,7 @@ fn transform_markdown_fields(
         if let Some(field_schema) = properties_obj.get(field_name) {
             if is_markdown_field(field_schema) {
                 if let Some(content) = field_value.as_str() {
-                    if let Ok(typst_markup) = mark_to_typst(content) {
+                    if let Ok(typst_markup) = mark_to_typst_with_options(content, options) {
                         result.insert(
                             field_name.clone(),
                             QuillValue::from_json(serde_json::json!(typst_markup)),
@@ -269,7 +440,7 @@ fn transform_markdown_fields(
     // Handle CARDS array recursively
     if let Some(cards_value) = result.get("CARDS") {
         if let Some(cards_array) = cards_value.as_array() {
-            let transformed_cards = transform_cards_array(schema, cards_array);
+            let transformed_cards = transform_cards_array(schema, cards_array, options);
             result.insert(
                 "CARDS".to_string(),
                 QuillValue::from_json(serde_json::Value::Array(transformed_cards)),
@@ -350,6 +521,7 @@ fn transform_markdown_fields(
 fn transform_cards_array(
     document_schema: &QuillValue,
     cards_array: &[serde_json::Value],
+    options: &MarkOptions,
 ) -> Vec<serde_json::Value> {
     let mut transformed_cards = Vec::new();
 
@@ -377,6 +549,7 @@ fn transform_cards_array(
                     let transformed_card_fields = transform_markdown_fields(
                         &card_fields,
                         &QuillValue::from_json(card_schema_json.clone()),
+                        options,
                     );
 
                     // Convert back to JSON Value
@@ -411,6 +584,11 @@ mod tests {
         assert!(backend.supported_formats().contains(&OutputFormat::Svg));
     }
 
+    #[test]
+    fn test_backend_conformance() {
+        quillmark_core::testing::assert_backend_conformance(&TypstBackend);
+    }
+
     #[test]
     fn test_is_markdown_field() {
         let markdown_schema = json!({
@@ -452,6 +630,48 @@ mod tests {
         assert!(!is_date_field(&non_string_date_schema));
     }
 
+    #[test]
+    fn test_mark_options_from_typst_section() {
+        let yaml = "quill:\n  name: m\n  version: \"1.0\"\n  backend: typst\n  description: T\n";
+        let config = QuillConfig::from_yaml(yaml).unwrap();
+        assert_eq!(mark_options(&config), MarkOptions::default());
+
+        let with_math = format!(
+            "{}typst:\n  math: true\n  math_passthrough: true\n  heading_labels: true\n",
+            yaml
+        );
+        let config = QuillConfig::from_yaml(&with_math).unwrap();
+        assert_eq!(
+            mark_options(&config),
+            MarkOptions {
+                math: true,
+                math_passthrough: true,
+                heading_labels: true,
+                ..MarkOptions::default()
+            }
+        );
+
+        for (value, expected) in [
+            ("4", ListIndent::Spaces(4)),
+            ("tab", ListIndent::Tab),
+            ("0", ListIndent::Spaces(2)),
+        ] {
+            let yaml = format!("{}typst:\n  list_indent: {}\n", yaml, value);
+            let config = QuillConfig::from_yaml(&yaml).unwrap();
+            assert_eq!(mark_options(&config).list_indent, expected);
+        }
+
+        for (value, expected) in [
+            ("true", Some("<!-- pagebreak -->")),
+            ("'\\pagebreak'", Some("\\pagebreak")),
+            ("false", None),
+        ] {
+            let yaml = format!("{}typst:\n  page_break: {}\n", yaml, value);
+            let config = QuillConfig::from_yaml(&yaml).unwrap();
+            assert_eq!(mark_options(&config).page_break_marker.as_deref(), expected);
+        }
+    }
+
     #[test]
     fn test_transform_markdown_fields_basic() {
         let schema = QuillValue::from_json(json!({
@@ -472,7 +692,7 @@ mod tests {
             QuillValue::from_json(json!("This is **bold** text.")),
         );
 
-        let result = transform_markdown_fields(&fields, &schema);
+        let result = transform_markdown_fields(&fields, &schema, &MarkOptions::default());
 
         // title should be unchanged
         assert_eq!(result.get("title").unwrap().as_str(), Some("My Title"));
@@ -499,7 +719,7 @@ mod tests {
         );
         fields.insert("count".to_string(), QuillValue::from_json(json!(42)));
 
-        let result = transform_markdown_fields(&fields, &schema);
+        let result = transform_markdown_fields(&fields, &schema, &MarkOptions::default());
 
         // All fields should be unchanged
         assert_eq!(result.get("title").unwrap().as_str(), Some("My Title"));
@@ -521,7 +741,7 @@ mod tests {
             QuillValue::from_json(json!("_italic_ text")),
         );
 
-        let result = transform_markdown_fields(&fields, &schema);
+        let result = transform_markdown_fields(&fields, &schema, &MarkOptions::default());
 
         let body = result.get("BODY").unwrap().as_str().unwrap();
         assert!(body.contains("#emph[italic]"));
@@ -544,7 +764,7 @@ mod tests {
             QuillValue::from_json(json!("My Title")),
         );
 
-        let result = transform_markdown_fields(&fields, &schema);
+        let result = transform_markdown_fields(&fields, &schema, &MarkOptions::default());
         let meta = result.get("__meta__").expect("missing __meta__").as_json();
 
         assert_eq!(meta["date_fields"], json!(["date"]));
@@ -568,7 +788,7 @@ mod tests {
         }));
 
         let fields = HashMap::new();
-        let result = transform_markdown_fields(&fields, &schema);
+        let result = transform_markdown_fields(&fields, &schema, &MarkOptions::default());
         let meta = result.get("__meta__").expect("missing __meta__").as_json();
 
         assert_eq!(meta["card_date_fields"]["indorsement"], json!(["date"]));
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
-use typst::diag::{FileError, FileResult};
+use typst::diag::{FileError, FileResult, PackageError};
 use typst::foundations::{Bytes, Datetime};
 use typst::syntax::{package::PackageSpec, FileId, Source, VirtualPath};
 use typst::text::{Font, FontBook};
@@ -8,7 +8,7 @@ use typst::utils::LazyHash;
 use typst::{Library, World};
 
 use crate::helper;
-use quillmark_core::QuillSource;
+use quillmark_core::{OutputFormat, QuillSource};
 
 static FALLBACK_REGULAR: &[u8] = include_bytes!("fonts/Figtree-Regular.ttf");
 static FALLBACK_BOLD: &[u8] = include_bytes!("fonts/Figtree-Bold.ttf");
@@ -18,7 +18,8 @@ static FALLBACK_ITALIC: &[u8] = include_bytes!("fonts/Figtree-Italic.ttf");
 ///
 /// Implements the Typst `World` trait to provide dynamic package loading,
 /// virtual path handling, and asset management for quill templates.
-/// Packages are loaded from `{quill}/packages/` and assets from `{quill}/assets/`.
+/// Packages are loaded from `{quill}/packages/` and assets from the quill's
+/// asset directory (`quill.asset_dir`, default `{quill}/assets/`).
 pub struct QuillWorld {
     library: LazyHash<Library>,
     book: LazyHash<FontBook>,
@@ -29,10 +30,14 @@ pub struct QuillWorld {
 }
 
 impl QuillWorld {
-    /// Create a new QuillWorld from a quill template and Typst content
+    /// Create a new QuillWorld from a quill template and Typst content.
+    ///
+    /// With a `format`, assets Quill.yaml limits to other formats are not
+    /// loaded.
     pub fn new(
         source: &QuillSource,
         main: &str,
+        format: Option<OutputFormat>,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let mut sources = HashMap::new();
         let mut binaries = HashMap::new();
@@ -42,7 +47,7 @@ impl QuillWorld {
         let mut fonts = Vec::new();
 
         // Load fonts from quill assets (eagerly loaded).
-        let font_data_list = Self::load_fonts_from_quill(source)?;
+        let font_data_list = Self::load_fonts_from_quill(source, format)?;
         for font_data in font_data_list {
             let font_bytes = Bytes::new(font_data);
             for font in Font::iter(font_bytes) {
@@ -63,7 +68,7 @@ impl QuillWorld {
         }
 
         // Load assets from quill's in-memory file system
-        Self::load_assets_from_quill(source, &mut binaries)?;
+        Self::load_assets_from_quill(source, format, &mut binaries)?;
 
         // Load packages from quill's in-memory file system. Quillmark does
         // not download external packages — every package a quill imports
@@ -95,12 +100,14 @@ impl QuillWorld {
     /// * `quill` - The quill template
     /// * `main` - The main Typst content to compile
     /// * `json_data` - JSON string containing document data
+    /// * `format` - The only output format, if known (see [`QuillWorld::new`])
     pub fn new_with_data(
         source: &QuillSource,
         main: &str,
         json_data: &str,
+        format: Option<OutputFormat>,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let mut world = Self::new(source, main)?;
+        let mut world = Self::new(source, main, format)?;
 
         // Inject the quillmark-helper package
         world.inject_helper_package(json_data);
@@ -108,6 +115,23 @@ impl QuillWorld {
         Ok(world)
     }
 
+    /// Error for a file the world does not have. Imports from a package the
+    /// quill does not bundle report the package, not a bare file path.
+    fn not_found(&self, id: FileId) -> FileError {
+        match id.package() {
+            Some(spec)
+                if !self
+                    .sources
+                    .keys()
+                    .chain(self.binaries.keys())
+                    .any(|known| known.package() == Some(spec)) =>
+            {
+                FileError::Package(PackageError::NotFound(spec.clone()))
+            }
+            _ => FileError::NotFound(id.vpath().as_rootless_path().to_owned()),
+        }
+    }
+
     /// Inject the quillmark-helper package with JSON data.
     fn inject_helper_package(&mut self, json_data: &str) {
         // Create the package spec
@@ -137,12 +161,16 @@ impl QuillWorld {
     /// Loads fonts from quill's in-memory file system.
     fn load_fonts_from_quill(
         source: &QuillSource,
+        format: Option<OutputFormat>,
     ) -> Result<Vec<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
         let mut font_data = Vec::new();
 
-        // Look for fonts in assets/fonts/ first
-        let fonts_paths = source.find_files("assets/fonts/*");
+        // Look for fonts in {asset_dir}/fonts/ first
+        let fonts_paths = source.find_files(format!("{}/fonts/*", source.asset_dir()));
         for font_path in fonts_paths {
+            if !source.asset_applies_to(&font_path.to_string_lossy(), format) {
+                continue;
+            }
             if let Some(ext) = font_path.extension() {
                 if matches!(
                     ext.to_string_lossy().to_lowercase().as_str(),
@@ -176,12 +204,16 @@ impl QuillWorld {
     /// Loads assets from quill's in-memory file system.
     fn load_assets_from_quill(
         source: &QuillSource,
+        format: Option<OutputFormat>,
         binaries: &mut HashMap<FileId, Bytes>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Get all files that start with "assets/"
-        let asset_paths = source.find_files("assets/*");
+        // Get all files under the quill's asset directory
+        let asset_paths = source.find_files(format!("{}/*", source.asset_dir()));
 
         for asset_path in asset_paths {
+            if !source.asset_applies_to(&asset_path.to_string_lossy(), format) {
+                continue;
+            }
             if let Some(contents) = source.get_file(&asset_path) {
                 // Create virtual path for the asset
                 let virtual_path = VirtualPath::new(asset_path.to_string_lossy().as_ref());
@@ -234,10 +266,7 @@ impl QuillWorld {
                         )?;
                     }
                     Err(e) => {
-                        eprintln!(
-                            "Warning: Failed to parse typst.toml for {}: {}",
-                            package_name, e
-                        );
+                        log::warn!("Failed to parse typst.toml for {}: {}", package_name, e);
                         // Continue with other packages
                     }
                 }
@@ -308,9 +337,10 @@ impl QuillWorld {
             let entrypoint_file_id = FileId::new(Some(spec.clone()), entrypoint_path);
 
             if !sources.contains_key(&entrypoint_file_id) {
-                eprintln!(
-                    "Warning: Entrypoint {} not found for package {}",
-                    entrypoint_name, spec.name
+                log::warn!(
+                    "Entrypoint {} not found for package {}",
+                    entrypoint_name,
+                    spec.name
                 );
             }
         }
@@ -338,9 +368,7 @@ impl World for QuillWorld {
         } else if let Some(source) = self.sources.get(&id) {
             Ok(source.clone())
         } else {
-            Err(FileError::NotFound(
-                id.vpath().as_rootless_path().to_owned(),
-            ))
+            Err(self.not_found(id))
         }
     }
 
@@ -348,9 +376,7 @@ impl World for QuillWorld {
         if let Some(bytes) = self.binaries.get(&id) {
             Ok(bytes.clone())
         } else {
-            Err(FileError::NotFound(
-                id.vpath().as_rootless_path().to_owned(),
-            ))
+            Err(self.not_found(id))
         }
     }
 
@@ -466,6 +492,22 @@ fn parse_package_toml(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use quillmark_core::FileTreeNode;
+
+    fn file(contents: &[u8]) -> FileTreeNode {
+        FileTreeNode::File {
+            contents: contents.to_vec(),
+        }
+    }
+
+    fn dir(entries: Vec<(&str, FileTreeNode)>) -> FileTreeNode {
+        FileTreeNode::Directory {
+            files: entries
+                .into_iter()
+                .map(|(name, node)| (name.to_string(), node))
+                .collect(),
+        }
+    }
 
     #[test]
     fn test_parse_package_toml() {
@@ -498,6 +540,95 @@ name = "minimal-package"
         assert_eq!(package_info.entrypoint, "lib.typ");
     }
 
+    #[test]
+    fn test_custom_asset_dir_is_loaded() {
+        let tree = dir(vec![
+            (
+                "Quill.yaml",
+                file(b"quill:\n  name: media_quill\n  version: \"1.0\"\n  backend: typst\n  description: Custom asset dir\n  asset_dir: media\n"),
+            ),
+            ("media", dir(vec![("note.txt", file(b"from media"))])),
+            ("assets", dir(vec![("note.txt", file(b"from assets"))])),
+        ]);
+        let source = QuillSource::from_tree(tree).expect("load source");
+        let world = QuillWorld::new(&source, "// Test", None).unwrap();
+
+        let id = |path: &str| FileId::new(None, VirtualPath::new(path));
+        assert_eq!(
+            world.file(id("media/note.txt")).unwrap().as_slice(),
+            b"from media"
+        );
+        assert!(world.file(id("assets/note.txt")).is_err());
+    }
+
+    #[test]
+    fn test_format_limited_asset_loaded_only_for_its_format() {
+        let tree = dir(vec![
+            (
+                "Quill.yaml",
+                file(b"quill:\n  name: print_quill\n  version: \"1.0\"\n  backend: typst\n  description: PDF-only asset\nassets:\n  assets/print.txt:\n    formats: [pdf]\n"),
+            ),
+            (
+                "assets",
+                dir(vec![
+                    ("print.txt", file(b"print only")),
+                    ("shared.txt", file(b"every format")),
+                ]),
+            ),
+        ]);
+        let source = QuillSource::from_tree(tree).expect("load source");
+        let id = |path: &str| FileId::new(None, VirtualPath::new(path));
+
+        let svg = QuillWorld::new(&source, "// Test", Some(OutputFormat::Svg)).unwrap();
+        assert!(svg.file(id("assets/print.txt")).is_err());
+        assert!(svg.file(id("assets/shared.txt")).is_ok());
+
+        for format in [Some(OutputFormat::Pdf), None] {
+            let world = QuillWorld::new(&source, "// Test", format).unwrap();
+            assert_eq!(
+                world.file(id("assets/print.txt")).unwrap().as_slice(),
+                b"print only"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bundled_package_in_custom_namespace() {
+        let toolkit = dir(vec![
+            (
+                "typst.toml",
+                file(b"[package]\nnamespace = \"company\"\nname = \"toolkit\"\nversion = \"1.0.0\"\nentrypoint = \"lib.typ\"\n"),
+            ),
+            ("lib.typ", file(b"#let greet(name) = \"Hello, \" + name")),
+        ]);
+        let tree = dir(vec![
+            (
+                "Quill.yaml",
+                file(b"quill:\n  name: pkg_quill\n  version: \"1.0\"\n  backend: typst\n  description: Namespaced package\n"),
+            ),
+            ("packages", dir(vec![("toolkit", toolkit)])),
+        ]);
+        let source = QuillSource::from_tree(tree).expect("load source");
+
+        let plate =
+            "#import \"@company/toolkit:1.0.0\": greet\n#assert.eq(greet(\"Ada\"), \"Hello, Ada\")";
+        crate::compile::compile_to_document(&source, plate, "{}")
+            .expect("namespaced package should resolve");
+
+        let err = crate::compile::compile_to_document(
+            &source,
+            "#import \"@other/toolkit:1.0.0\": greet",
+            "{}",
+        )
+        .expect_err("unknown namespace should fail");
+        let message = format!("{:?}", err.diagnostics());
+        assert!(
+            message.contains("@other/toolkit:1.0.0"),
+            "error should name the package: {}",
+            message
+        );
+    }
+
     #[test]
     fn test_asset_fonts_have_priority() {
         use std::collections::HashMap;
@@ -545,7 +676,7 @@ name = "minimal-package"
 
         let tree = walk(&quill_path, &quill_path).expect("walk fixture");
         let source = QuillSource::from_tree(tree).expect("load source");
-        let world = QuillWorld::new(&source, "// Test").unwrap();
+        let world = QuillWorld::new(&source, "// Test", None).unwrap();
 
         // Asset fonts should be loaded
         assert!(!world.fonts.is_empty(), "Should have asset fonts loaded");
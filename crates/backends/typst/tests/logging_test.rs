@@ -0,0 +1,60 @@
+//! The backend reports progress and warnings through the `log` facade.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use quillmark_core::{Backend, FileTreeNode, QuillSource};
+use quillmark_typst::TypstBackend;
+
+struct CaptureLogger {
+    records: Mutex<Vec<(log::Level, String)>>,
+}
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.records
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn test_compile_message_logged_at_info() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let quill_yaml = "quill:\n  name: \"logged_quill\"\n  version: \"1.0\"\n  backend: \"typst\"\n  description: \"Test\"\n";
+    let mut files = HashMap::new();
+    files.insert(
+        "Quill.yaml".to_string(),
+        FileTreeNode::File {
+            contents: quill_yaml.as_bytes().to_vec(),
+        },
+    );
+    let source = QuillSource::from_tree(FileTreeNode::Directory { files }).unwrap();
+
+    TypstBackend
+        .open("Hello", &source, &serde_json::json!({}))
+        .unwrap();
+
+    let records = LOGGER.records.lock().unwrap();
+    assert!(
+        records.contains(&(
+            log::Level::Info,
+            "Typst backend compiling for quill: logged_quill".to_string()
+        )),
+        "records: {:?}",
+        *records
+    );
+}
@@ -1,10 +1,13 @@
 use crate::errors::{CliError, Result};
-use crate::output::{derive_output_path, OutputWriter};
+use crate::output::{derive_output_path, numbered_output_path, OutputWriter};
 use clap::Parser;
-use quillmark::{Document, Quillmark};
+use quillmark::{Document, DumpFormat, ParseOutput, Quillmark, RenderResult, RenderTimings};
+use quillmark_core::quill::is_contained_path;
 use quillmark_core::{OutputFormat, RenderOptions};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 pub struct RenderArgs {
@@ -12,10 +15,14 @@ pub struct RenderArgs {
     #[arg(value_name = "QUILL_PATH")]
     quill: PathBuf,
 
-    /// Path to markdown file with YAML frontmatter
+    /// Path to markdown file with YAML frontmatter (`-` reads standard input)
     #[arg(value_name = "MARKDOWN_FILE")]
     markdown_file: Option<PathBuf>,
 
+    /// Read markdown from standard input (requires --output or --stdout)
+    #[arg(long, conflicts_with_all = ["markdown_file", "output_dir"])]
+    stdin: bool,
+
     /// Output file path (default: derived from input filename)
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
@@ -28,7 +35,15 @@ pub struct RenderArgs {
     #[arg(long)]
     stdout: bool,
 
-    /// Show detailed processing information
+    /// Write every artifact into this directory (numbered per page for SVG/PNG)
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "stdout"])]
+    output_dir: Option<PathBuf>,
+
+    /// Copy assets referenced by the plate into the output directory
+    #[arg(long, requires = "output_dir")]
+    bundle: bool,
+
+    /// Show detailed processing information, including per-stage timing
     #[arg(short, long)]
     verbose: bool,
 
@@ -39,6 +54,10 @@ pub struct RenderArgs {
     /// Output intermediate JSON data to file
     #[arg(long, value_name = "DATA_FILE")]
     output_data: Option<PathBuf>,
+
+    /// Print the data the plate receives to stderr before rendering: json, yaml
+    #[arg(long, value_name = "FORMAT")]
+    dump_context: Option<String>,
 }
 
 pub fn execute(args: RenderArgs) -> Result<()> {
@@ -62,57 +81,89 @@ pub fn execute(args: RenderArgs) -> Result<()> {
         println!("Quill loaded: {}", quill.source().name());
     }
 
+    let read_stdin = args.stdin || args.markdown_file.as_deref() == Some(Path::new("-"));
+
     // Determine if we have a markdown file or need to use example content
-    let (parse_output, markdown_path_for_output) =
-        if let Some(ref markdown_path) = args.markdown_file {
-            // Validate markdown file exists
-            if !markdown_path.exists() {
-                return Err(CliError::InvalidArgument(format!(
-                    "Markdown file not found: {}",
-                    markdown_path.display()
-                )));
-            }
+    let (parse_output, parse_time, markdown_path_for_output) = if read_stdin {
+        // No input filename to derive an output path from.
+        if args.output.is_none() && !args.stdout {
+            return Err(CliError::InvalidArgument(
+                "Reading markdown from stdin requires --output <FILE> or --stdout".to_string(),
+            ));
+        }
+        if args.output_dir.is_some() {
+            return Err(CliError::InvalidArgument(
+                "--output-dir cannot be used when reading markdown from stdin".to_string(),
+            ));
+        }
 
-            if args.verbose {
-                println!("Reading markdown from: {}", markdown_path.display());
-            }
+        if args.verbose {
+            println!("Reading markdown from stdin");
+        }
 
-            // Read markdown file
-            let markdown = fs::read_to_string(markdown_path)?;
+        let mut markdown = String::new();
+        io::stdin().read_to_string(&mut markdown)?;
 
-            // Parse markdown
-            let output = Document::from_markdown_with_warnings(&markdown)?;
+        // Empty stdin renders an empty document for this quill.
+        if markdown.trim().is_empty() {
+            markdown = format!("---\nQUILL: {}\n---\n", quill.source().name());
+        }
 
-            if args.verbose {
-                println!("Markdown parsed successfully");
-            }
-            (output, Some(markdown_path.clone()))
-        } else {
-            // Get example content
-            let markdown = quill
-                .source()
-                .example()
-                .map(|s| s.to_string())
-                .ok_or_else(|| {
-                    CliError::InvalidArgument(format!(
-                        "Quill '{}' does not have example content",
-                        quill.source().name()
-                    ))
-                })?;
-
-            if args.verbose {
-                println!("Using example content from quill");
-            }
+        let (output, parse_time) = parse_markdown(&markdown)?;
 
-            // Parse markdown
-            let output = Document::from_markdown_with_warnings(&markdown)?;
+        if args.verbose {
+            println!("Markdown parsed successfully");
+        }
+        (output, parse_time, None)
+    } else if let Some(ref markdown_path) = args.markdown_file {
+        // Validate markdown file exists
+        if !markdown_path.exists() {
+            return Err(CliError::InvalidArgument(format!(
+                "Markdown file not found: {}",
+                markdown_path.display()
+            )));
+        }
 
-            if args.verbose {
-                println!("Example markdown parsed successfully");
-            }
+        if args.verbose {
+            println!("Reading markdown from: {}", markdown_path.display());
+        }
 
-            (output, None)
-        };
+        // Read markdown file
+        let markdown = fs::read_to_string(markdown_path)?;
+
+        // Parse markdown
+        let (output, parse_time) = parse_markdown(&markdown)?;
+
+        if args.verbose {
+            println!("Markdown parsed successfully");
+        }
+        (output, parse_time, Some(markdown_path.clone()))
+    } else {
+        // Get example content
+        let markdown = quill
+            .source()
+            .example()
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                CliError::InvalidArgument(format!(
+                    "Quill '{}' does not have example content",
+                    quill.source().name()
+                ))
+            })?;
+
+        if args.verbose {
+            println!("Using example content from quill");
+        }
+
+        // Parse markdown
+        let (output, parse_time) = parse_markdown(&markdown)?;
+
+        if args.verbose {
+            println!("Example markdown parsed successfully");
+        }
+
+        (output, parse_time, None)
+    };
     let (parsed, parse_warnings) = (parse_output.document, parse_output.warnings);
 
     if args.verbose {
@@ -137,11 +188,27 @@ pub fn execute(args: RenderArgs) -> Result<()> {
         println!("Rendering to format: {:?}", output_format);
     }
 
-    // Handle output-data
-    if let Some(data_path) = args.output_data {
-        let json_data = quill
-            .compile_data(&parsed)
-            .map_err(|e| CliError::Render(e))?;
+    let render_options = RenderOptions::builder()
+        .output_format(output_format)
+        .build();
+
+    if let Some(ref format) = args.dump_context {
+        let dump_format = match format.to_lowercase().as_str() {
+            "json" => DumpFormat::Json,
+            "yaml" => DumpFormat::Yaml,
+            _ => {
+                return Err(CliError::InvalidArgument(format!(
+                    "Invalid dump format: {}. Must be one of: json, yaml",
+                    format
+                )));
+            }
+        };
+        eprintln!("{}", dump_format.dump(&quill.compile_data(&parsed)?));
+    }
+
+    // Render, capturing the plate data in the same pass when --output-data is set
+    let mut result = if let Some(data_path) = args.output_data {
+        let (result, json_data) = quill.render_with_data(&parsed, &render_options)?;
         let f = std::fs::File::create(&data_path).map_err(|e| {
             CliError::Io(std::io::Error::new(
                 e.kind(),
@@ -161,16 +228,14 @@ pub fn execute(args: RenderArgs) -> Result<()> {
         if args.verbose && !args.quiet {
             println!("JSON data written to: {}", data_path.display());
         }
-    }
-
-    // Render
-    let mut result = quill.render(
-        &parsed,
-        &RenderOptions {
-            output_format: Some(output_format),
-            ..Default::default()
-        },
-    )?;
+        result
+    } else if args.verbose {
+        let (result, timings) = quill.render_timed(&parsed, &render_options)?;
+        print_timings(parse_time, &timings, &result);
+        result
+    } else {
+        quill.render(&parsed, &render_options)?
+    };
 
     // Merge parse-time warnings into the render result so downstream tooling
     // sees them in a single channel.
@@ -181,8 +246,64 @@ pub fn execute(args: RenderArgs) -> Result<()> {
         crate::errors::print_warnings(&result.warnings);
     }
 
-    // Get the first artifact (there should only be one for single format render)
-    let artifact = result.artifacts.first().ok_or_else(|| {
+    let stem = markdown_path_for_output
+        .as_deref()
+        .and_then(Path::file_stem)
+        .and_then(|s| s.to_str())
+        .unwrap_or("example")
+        .to_string();
+
+    if let Some(dir) = args.output_dir {
+        let count = result.artifacts.len();
+        if count == 0 {
+            return Err(CliError::InvalidArgument(
+                "No artifacts produced from rendering".to_string(),
+            ));
+        }
+        // Checked before writing anything: the plate names these paths, and
+        // `..` must not place a copy outside the output directory.
+        let bundle_assets = if args.bundle {
+            quill.referenced_assets()
+        } else {
+            Vec::new()
+        };
+        if let Some(asset) = bundle_assets.iter().find(|a| !is_contained_path(a)) {
+            return Err(CliError::InvalidArgument(format!(
+                "Refusing to bundle asset outside the quill: {}",
+                asset
+            )));
+        }
+        for (i, artifact) in result.artifacts.iter().enumerate() {
+            let path = if count == 1 {
+                dir.join(format!("{}.{}", stem, args.format))
+            } else {
+                numbered_output_path(&dir, &stem, artifact.page.unwrap_or(i) + 1, &args.format)
+            };
+            OutputWriter::new(false, Some(path), args.quiet).write(&artifact.bytes)?;
+        }
+
+        if args.bundle {
+            for asset in bundle_assets {
+                match quill.source().get_file(&asset) {
+                    Some(bytes) => {
+                        OutputWriter::new(false, Some(dir.join(&asset)), args.quiet)
+                            .write(bytes)?;
+                    }
+                    None if !args.quiet => {
+                        eprintln!("Warning: referenced asset not found in quill: {}", asset);
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        if args.verbose && !args.quiet {
+            println!("Rendering completed successfully");
+        }
+        return Ok(());
+    }
+
+    let artifact = result.primary().ok_or_else(|| {
         CliError::InvalidArgument("No artifacts produced from rendering".to_string())
     })?;
 
@@ -208,3 +329,28 @@ pub fn execute(args: RenderArgs) -> Result<()> {
 
     Ok(())
 }
+
+fn parse_markdown(markdown: &str) -> Result<(ParseOutput, Duration)> {
+    let started = Instant::now();
+    let output = Document::from_markdown_with_warnings(markdown)?;
+    Ok((output, started.elapsed()))
+}
+
+/// Print one `key=value` line per render stage to stderr.
+fn print_timings(parse: Duration, timings: &RenderTimings, result: &RenderResult) {
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    eprintln!("[timing] stage=parse ms={:.3}", ms(parse));
+    eprintln!("[timing] stage=data ms={:.3}", ms(timings.data));
+    eprintln!("[timing] stage=compile ms={:.3}", ms(timings.compile));
+    eprintln!("[timing] stage=output ms={:.3}", ms(timings.output));
+    eprintln!(
+        "[timing] pages={} artifacts={} bytes={}",
+        timings.page_count,
+        result.artifacts.len(),
+        result
+            .artifacts
+            .iter()
+            .map(|a| a.bytes.len())
+            .sum::<usize>()
+    );
+}
@@ -2,10 +2,10 @@ use crate::errors::{CliError, Result};
 use clap::Parser;
 use quillmark::Quillmark;
 use quillmark_core::quill::{CardSchema, FieldSchema, FieldType, QuillConfig};
-use quillmark_core::QuillValue;
+use quillmark_core::{QuillValue, Version};
 use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 pub struct ValidateArgs {
@@ -16,6 +16,12 @@ pub struct ValidateArgs {
     /// Show verbose output with all validation details
     #[arg(short, long)]
     verbose: bool,
+
+    /// Rewrite Quill.yaml to fix common problems before validating,
+    /// keeping the original as Quill.yaml.bak (or Quill.yaml.N.bak when a
+    /// backup already exists)
+    #[arg(long)]
+    fix: bool,
 }
 
 /// Validation issue severity
@@ -110,7 +116,23 @@ pub fn execute(args: ValidateArgs) -> Result<()> {
     let mut result = ValidationResult::new();
 
     // Step 1: Parse the YAML config first (before full Quill load)
-    let yaml_content = fs::read_to_string(&quill_yaml_path).map_err(CliError::Io)?;
+    let mut yaml_content = fs::read_to_string(&quill_yaml_path).map_err(CliError::Io)?;
+
+    if args.fix {
+        let fix = fix_quill_yaml(&args.quill_path, &yaml_content);
+        for message in &fix.skipped {
+            result.add_error(format!("Not fixed: {}", message));
+        }
+        if !fix.changes.is_empty() {
+            let backup = backup_path(&args.quill_path);
+            fs::copy(&quill_yaml_path, &backup).map_err(CliError::Io)?;
+            fs::write(&quill_yaml_path, &fix.yaml).map_err(CliError::Io)?;
+            for change in &fix.changes {
+                println!("Fixed: {}", change);
+            }
+            yaml_content = fix.yaml;
+        }
+    }
 
     let config = match QuillConfig::from_yaml(&yaml_content) {
         Ok(c) => c,
@@ -157,7 +179,11 @@ pub fn execute(args: ValidateArgs) -> Result<()> {
             validate_defaults_against_schema(&quill, &config, &mut result);
         }
         Err(e) => {
-            result.add_error(format!("Failed to load Quill: {}", e));
+            let mut message = format!("Failed to load Quill: {}", e);
+            for hint in e.diagnostics().into_iter().filter_map(|d| d.hint.as_ref()) {
+                message.push_str(&format!("\n  hint: {}", hint));
+            }
+            result.add_error(message);
         }
     }
 
@@ -174,6 +200,154 @@ pub fn execute(args: ValidateArgs) -> Result<()> {
     }
 }
 
+/// Outcome of [`fix_quill_yaml`]
+#[derive(Debug, Default)]
+struct QuillYamlFix {
+    yaml: String,
+    changes: Vec<String>,
+    skipped: Vec<String>,
+}
+
+/// Fix common problems in the `quill:` section of a Quill.yaml.
+///
+/// Works on the text rather than a parsed config so comments and layout
+/// survive, and so a file too broken to parse can still be fixed:
+/// - a missing `name` is set to the quill's directory name, skipping
+///   version directories (`<name>/<version>/`); a directory name that is not
+///   a valid quill name is reported, not written
+/// - a `plate_file` that does not exist is pointed at the quill's only
+///   `.typ` file; with several candidates it is reported, not guessed
+fn fix_quill_yaml(quill_path: &Path, yaml: &str) -> QuillYamlFix {
+    let mut fix = QuillYamlFix::default();
+    let mut lines: Vec<String> = yaml.lines().map(str::to_string).collect();
+
+    let Some(section) = lines.iter().position(|line| line.trim_end() == "quill:") else {
+        fix.skipped.push("no `quill:` section to fix".to_string());
+        fix.yaml = yaml.to_string();
+        return fix;
+    };
+    let section_end = lines[section + 1..]
+        .iter()
+        .position(|line| {
+            !line.trim().is_empty() && !line.starts_with(' ') && !line.starts_with('#')
+        })
+        .map_or(lines.len(), |offset| section + 1 + offset);
+    let indent = lines[section + 1..section_end]
+        .iter()
+        .find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map_or("  ".to_string(), |line| {
+            line[..line.len() - line.trim_start().len()].to_string()
+        });
+    let key_line = |lines: &[String], key: &str| {
+        (section + 1..section_end).find(|&i| {
+            lines[i]
+                .strip_prefix(indent.as_str())
+                .and_then(|rest| rest.strip_prefix(key))
+                .is_some_and(|rest| rest.starts_with(':'))
+        })
+    };
+
+    if let Some(i) = key_line(&lines, "plate_file") {
+        let plate_file = yaml_scalar(&lines[i][indent.len() + "plate_file:".len()..]).to_string();
+        if !quill_path.join(&plate_file).exists() {
+            let mut candidates: Vec<String> = fs::read_dir(quill_path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name.ends_with(".typ"))
+                .collect();
+            candidates.sort();
+            match candidates.as_slice() {
+                [candidate] => {
+                    lines[i] = format!("{}plate_file: {}", indent, yaml_string(candidate));
+                    fix.changes.push(format!(
+                        "plate_file '{}' does not exist; set to '{}'",
+                        plate_file, candidate
+                    ));
+                }
+                _ => fix.skipped.push(format!(
+                    "plate_file '{}' does not exist and {} .typ files could replace it: {:?}",
+                    plate_file,
+                    candidates.len(),
+                    candidates
+                )),
+            }
+        }
+    }
+
+    if key_line(&lines, "name").is_none() {
+        let dir_name = quill_path.canonicalize().ok().and_then(|path| {
+            path.ancestors()
+                .filter_map(|dir| dir.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .find(|name| name.parse::<Version>().is_err())
+        });
+        match dir_name {
+            Some(name) if !QuillConfig::is_valid_quill_name(&name) => fix.skipped.push(format!(
+                "missing name, and the directory name '{}' is not snake_case",
+                name
+            )),
+            Some(name) => {
+                lines.insert(
+                    section + 1,
+                    format!("{}name: {}", indent, yaml_string(&name)),
+                );
+                fix.changes.push(format!(
+                    "added missing name '{}' from the directory name",
+                    name
+                ));
+            }
+            None => fix
+                .skipped
+                .push("missing name, and the directory has no name to use".to_string()),
+        }
+    }
+
+    fix.yaml = lines.join("\n");
+    if yaml.ends_with('\n') {
+        fix.yaml.push('\n');
+    }
+    fix
+}
+
+/// The scalar in a `key: value` line's value part, unquoted and without a
+/// trailing `# comment`.
+fn yaml_scalar(value: &str) -> &str {
+    let value = value.trim();
+    if let Some(quote @ ('"' | '\'')) = value.chars().next() {
+        if let Some(end) = value[1..].find(quote) {
+            return &value[1..1 + end];
+        }
+    }
+    if value.starts_with('#') {
+        return "";
+    }
+    value
+        .find(" #")
+        .map_or(value, |comment| value[..comment].trim_end())
+}
+
+/// First free backup name for Quill.yaml: `Quill.yaml.bak`, then
+/// `Quill.yaml.1.bak`, `Quill.yaml.2.bak`, ... so earlier backups are kept.
+/// The `.bak` suffix keeps them out of the bundle via [`QuillIgnore::defaults`].
+///
+/// [`QuillIgnore::defaults`]: quillmark_core::QuillIgnore::defaults
+fn backup_path(quill_path: &Path) -> PathBuf {
+    let mut path = quill_path.join("Quill.yaml.bak");
+    let mut n = 1;
+    while path.exists() {
+        path = quill_path.join(format!("Quill.yaml.{}.bak", n));
+        n += 1;
+    }
+    path
+}
+
+/// Double-quoted YAML scalar; JSON string syntax is valid YAML.
+fn yaml_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
 fn validate_file_references(
     quill_path: &PathBuf,
     config: &QuillConfig,
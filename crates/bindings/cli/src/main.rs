@@ -31,6 +31,7 @@ enum Commands {
 
 fn main() {
     let cli = Cli::parse();
+    output::init_logger();
 
     let result = match cli.command {
         Commands::Render(args) => commands::render::execute(args),
@@ -53,3 +53,39 @@ pub fn derive_output_path(markdown_path: &Path, format: &str) -> PathBuf {
     output.set_extension(format);
     output
 }
+
+/// Build `<dir>/<stem>-<index>.<format>` for one page of a multi-file render
+pub fn numbered_output_path(dir: &Path, stem: &str, index: usize, format: &str) -> PathBuf {
+    dir.join(format!("{}-{}.{}", stem, index, format))
+}
+
+/// Prints library warnings to stderr, the way the backends reported them
+/// before they logged through the `log` facade.
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            let prefix = match record.level() {
+                log::Level::Error => "Error",
+                _ => "Warning",
+            };
+            eprintln!("{}: {}", prefix, record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Route `log` warnings and errors from the libraries to stderr.
+pub fn init_logger() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(log::LevelFilter::Warn);
+    }
+}
@@ -0,0 +1,80 @@
+//! Integration tests for `quillmark render --output-dir`.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn write_quill(root: &std::path::Path) -> std::path::PathBuf {
+    let quill_path = root.join("paged");
+    fs::create_dir_all(quill_path.join("assets")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        "quill:\n  name: \"paged\"\n  version: \"1.0\"\n  backend: \"typst\"\n  plate_file: \"plate.typ\"\n  description: \"Test\"\n",
+    )
+    .unwrap();
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#let note = read(\"assets/note.txt\")\nFirst page\n#pagebreak()\n#note",
+    )
+    .unwrap();
+    fs::write(quill_path.join("assets/note.txt"), "Second page").unwrap();
+    quill_path
+}
+
+#[test]
+fn test_render_svg_into_output_dir_writes_numbered_pages() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = write_quill(temp_dir.path());
+    let markdown_path = temp_dir.path().join("doc.md");
+    fs::write(&markdown_path, "---\nQUILL: paged\n---\n").unwrap();
+    let out_dir = temp_dir.path().join("out");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_quillmark"))
+        .arg("render")
+        .arg(&quill_path)
+        .arg(&markdown_path)
+        .args(["--format", "svg", "--quiet", "--bundle", "--output-dir"])
+        .arg(&out_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    for page in ["doc-1.svg", "doc-2.svg"] {
+        let svg = fs::read_to_string(out_dir.join(page)).unwrap();
+        assert!(svg.contains("<svg"), "{} is not an SVG", page);
+    }
+    assert!(!out_dir.join("doc-3.svg").exists());
+    assert_eq!(
+        fs::read_to_string(out_dir.join("assets/note.txt")).unwrap(),
+        "Second page"
+    );
+}
+
+#[test]
+fn test_bundle_rejects_asset_outside_quill() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = write_quill(temp_dir.path());
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#if false { read(\"../../escape.txt\") }\nPage",
+    )
+    .unwrap();
+    fs::write(quill_path.join("escape.txt"), "secret").unwrap();
+    let markdown_path = temp_dir.path().join("doc.md");
+    fs::write(&markdown_path, "---\nQUILL: paged\n---\n").unwrap();
+    let out_dir = temp_dir.path().join("nested").join("out");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_quillmark"))
+        .arg("render")
+        .arg(&quill_path)
+        .arg(&markdown_path)
+        .args(["--format", "pdf", "--quiet", "--bundle", "--output-dir"])
+        .arg(&out_dir)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Refusing to bundle asset outside the quill: ../../escape.txt"));
+    assert!(!temp_dir.path().join("escape.txt").exists());
+    assert!(!out_dir.join("doc.pdf").exists());
+}
@@ -0,0 +1,74 @@
+//! Integration tests for `quillmark render --stdin`.
+
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+fn write_quill(root: &std::path::Path) -> std::path::PathBuf {
+    let quill_path = root.join("piped");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        "quill:\n  name: \"piped\"\n  version: \"1.0\"\n  backend: \"typst\"\n  plate_file: \"plate.typ\"\n  description: \"Test\"\n",
+    )
+    .unwrap();
+    fs::write(quill_path.join("plate.typ"), "Piped document").unwrap();
+    quill_path
+}
+
+fn render_stdin(quill_path: &std::path::Path, args: &[&str], input: &str) -> bool {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_quillmark"))
+        .arg("render")
+        .arg(quill_path)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait().unwrap().success()
+}
+
+#[test]
+fn test_render_from_stdin_writes_pdf() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = write_quill(temp_dir.path());
+    let output = temp_dir.path().join("out.pdf");
+    let output_arg = output.to_str().unwrap();
+
+    assert!(render_stdin(
+        &quill_path,
+        &["--stdin", "--quiet", "-o", output_arg],
+        "---\nQUILL: piped\n---\n\nHello from a pipe.\n",
+    ));
+    assert!(fs::read(&output).unwrap().starts_with(b"%PDF"));
+
+    // `-` is accepted in place of the markdown path, and empty input renders
+    // an empty document.
+    fs::remove_file(&output).unwrap();
+    assert!(render_stdin(
+        &quill_path,
+        &["-", "--quiet", "-o", output_arg],
+        ""
+    ));
+    assert!(fs::read(&output).unwrap().starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_render_from_stdin_requires_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = write_quill(temp_dir.path());
+
+    assert!(!render_stdin(
+        &quill_path,
+        &["--stdin", "--quiet"],
+        "---\nQUILL: piped\n---\n",
+    ));
+}
@@ -0,0 +1,160 @@
+//! Integration tests for `quillmark validate --fix`.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+const NAMELESS_YAML: &str = "quill:\n  # no name yet\n  version: \"1.0\"\n  backend: \"typst\"\n  plate_file: \"plate.typ\"\n  description: \"Test\"\n";
+
+fn validate_fix(quill_path: &std::path::Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_quillmark"))
+        .arg("validate")
+        .arg(quill_path)
+        .arg("--fix")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_validate_fix_adds_missing_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("letter");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(quill_path.join("Quill.yaml"), NAMELESS_YAML).unwrap();
+    fs::write(quill_path.join("plate.typ"), "#rect(width: 1cm)").unwrap();
+
+    let output = validate_fix(&quill_path);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Fixed: added missing name 'letter'"));
+
+    assert_eq!(
+        fs::read_to_string(quill_path.join("Quill.yaml")).unwrap(),
+        "quill:\n  name: \"letter\"\n  # no name yet\n  version: \"1.0\"\n  backend: \"typst\"\n  plate_file: \"plate.typ\"\n  description: \"Test\"\n"
+    );
+    assert_eq!(
+        fs::read_to_string(quill_path.join("Quill.yaml.bak")).unwrap(),
+        NAMELESS_YAML
+    );
+}
+
+#[test]
+fn test_validate_fix_refuses_ambiguous_plate() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("letter");
+    fs::create_dir_all(&quill_path).unwrap();
+    let yaml = NAMELESS_YAML.replace("plate.typ", "missing.typ");
+    fs::write(quill_path.join("Quill.yaml"), &yaml).unwrap();
+    fs::write(quill_path.join("a.typ"), "A").unwrap();
+    fs::write(quill_path.join("b.typ"), "B").unwrap();
+
+    let output = validate_fix(&quill_path);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Not fixed: plate_file 'missing.typ' does not exist"));
+    let fixed = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    assert!(fixed.contains("plate_file: \"missing.typ\""));
+    assert!(fixed.contains("name: \"letter\""));
+}
+
+#[test]
+fn test_validate_fix_names_versioned_quill_after_parent() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("letter").join("0.2.0");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(quill_path.join("Quill.yaml"), NAMELESS_YAML).unwrap();
+    fs::write(quill_path.join("plate.typ"), "#rect(width: 1cm)").unwrap();
+
+    let output = validate_fix(&quill_path);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(fs::read_to_string(quill_path.join("Quill.yaml"))
+        .unwrap()
+        .contains("name: \"letter\""));
+}
+
+#[test]
+fn test_validate_fix_refuses_invalid_directory_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("my-letter");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(quill_path.join("Quill.yaml"), NAMELESS_YAML).unwrap();
+    fs::write(quill_path.join("plate.typ"), "#rect(width: 1cm)").unwrap();
+
+    let output = validate_fix(&quill_path);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Not fixed: missing name, and the directory name 'my-letter' is not snake_case"));
+    assert_eq!(
+        fs::read_to_string(quill_path.join("Quill.yaml")).unwrap(),
+        NAMELESS_YAML
+    );
+    assert!(!quill_path.join("Quill.yaml.bak").exists());
+}
+
+#[test]
+fn test_validate_fix_ignores_plate_file_comment() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("letter");
+    fs::create_dir_all(&quill_path).unwrap();
+    let yaml = NAMELESS_YAML
+        .replace("  # no name yet\n", "  name: letter\n")
+        .replace("\"plate.typ\"", "plate.typ # main template");
+    fs::write(quill_path.join("Quill.yaml"), &yaml).unwrap();
+    fs::write(quill_path.join("plate.typ"), "#rect(width: 1cm)").unwrap();
+    fs::write(quill_path.join("other.typ"), "#rect(width: 2cm)").unwrap();
+
+    let output = validate_fix(&quill_path);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Fixed:"));
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Not fixed:"));
+    assert_eq!(
+        fs::read_to_string(quill_path.join("Quill.yaml")).unwrap(),
+        yaml
+    );
+}
+
+#[test]
+fn test_validate_fix_keeps_earlier_backups_out_of_the_bundle() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("letter");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(quill_path.join("plate.typ"), "#rect(width: 1cm)").unwrap();
+
+    let second = NAMELESS_YAML.replace("Test", "Second run");
+    for yaml in [NAMELESS_YAML, second.as_str()] {
+        fs::write(quill_path.join("Quill.yaml"), yaml).unwrap();
+        let output = validate_fix(&quill_path);
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    assert_eq!(
+        fs::read_to_string(quill_path.join("Quill.yaml.bak")).unwrap(),
+        NAMELESS_YAML
+    );
+    assert_eq!(
+        fs::read_to_string(quill_path.join("Quill.yaml.1.bak")).unwrap(),
+        second
+    );
+
+    let quill = quillmark::Quillmark::new()
+        .quill_from_path(&quill_path)
+        .unwrap();
+    assert!(quill.source().file_exists("plate.typ"));
+    assert!(!quill.source().file_exists("Quill.yaml.bak"));
+    assert!(!quill.source().file_exists("Quill.yaml.1.bak"));
+}
@@ -23,6 +23,9 @@ pub fn convert_edit_error(err: EditError) -> PyErr {
         EditError::InvalidFieldName(_) => "InvalidFieldName",
         EditError::InvalidTagName(_) => "InvalidTagName",
         EditError::IndexOutOfRange { .. } => "IndexOutOfRange",
+        EditError::ReferenceCycle(_) => "ReferenceCycle",
+        EditError::ReferencesTooDeep { .. } => "ReferencesTooDeep",
+        EditError::InterpolationTooLarge { .. } => "InterpolationTooLarge",
     };
     PyEditError::new_err(format!("[EditError::{}] {}", variant, err))
 }
@@ -58,7 +61,9 @@ pub fn convert_render_error(err: RenderError) -> PyErr {
         | RenderError::FormatNotSupported { diag }
         | RenderError::UnsupportedBackend { diag }
         | RenderError::ValidationFailed { diag }
-        | RenderError::QuillConfig { diag } => {
+        | RenderError::QuillConfig { diag }
+        | RenderError::TooManyPages { diag }
+        | RenderError::OutputFailed { diag } => {
             with_diag_attached(py, QuillmarkError::new_err(diag.message.clone()), *diag)
         }
     })
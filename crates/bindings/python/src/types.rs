@@ -135,7 +135,7 @@ impl PyQuill {
     #[getter]
     fn supported_formats(&self) -> Vec<PyOutputFormat> {
         self.inner
-            .supported_formats()
+            .output_formats()
             .iter()
             .map(|f| (*f).into())
             .collect()
@@ -147,10 +147,8 @@ impl PyQuill {
         doc: PyRef<'_, PyDocument>,
         format: Option<PyOutputFormat>,
     ) -> PyResult<PyRenderResult> {
-        let opts = quillmark_core::RenderOptions {
-            output_format: format.map(OutputFormat::from),
-            ..Default::default()
-        };
+        let mut opts = quillmark_core::RenderOptions::default();
+        opts.output_format = format.map(OutputFormat::from);
         let mut result = self
             .inner
             .render(&doc.inner, &opts)
@@ -553,11 +551,9 @@ impl PyRenderSession {
         format: Option<PyOutputFormat>,
         pages: Option<Vec<usize>>,
     ) -> PyResult<PyRenderResult> {
-        let opts = quillmark::RenderOptions {
-            output_format: format.map(OutputFormat::from),
-            ppi: None,
-            pages,
-        };
+        let mut opts = quillmark::RenderOptions::default();
+        opts.output_format = format.map(OutputFormat::from);
+        opts.pages = pages;
         let result = self.inner.render(&opts).map_err(convert_render_error)?;
         Ok(PyRenderResult { inner: result })
     }
@@ -573,6 +569,7 @@ impl PyRenderResult {
             .map(|a| PyArtifact {
                 inner: a.bytes.clone(),
                 output_format: a.output_format,
+                page: a.page,
             })
             .collect()
     }
@@ -598,6 +595,7 @@ impl PyRenderResult {
 pub struct PyArtifact {
     pub(crate) inner: Vec<u8>,
     pub(crate) output_format: OutputFormat,
+    pub(crate) page: Option<usize>,
 }
 
 #[pymethods]
@@ -612,6 +610,12 @@ impl PyArtifact {
         self.output_format.into()
     }
 
+    /// 0-based page index for SVG/PNG artifacts, `None` for PDF/TXT.
+    #[getter]
+    fn page(&self) -> Option<usize> {
+        self.page
+    }
+
     fn save(&self, path: String) -> PyResult<()> {
         std::fs::write(&path, &self.inner).map_err(|e| {
             PyErr::new::<crate::errors::QuillmarkError, _>(format!(
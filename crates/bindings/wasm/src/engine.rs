@@ -367,7 +367,7 @@ impl Quill {
 
         let formats: Vec<serde_json::Value> = self
             .inner
-            .supported_formats()
+            .output_formats()
             .iter()
             .map(|f| {
                 let wasm_format: crate::types::OutputFormat = (*f).into();
@@ -827,6 +827,9 @@ fn edit_error_to_js(err: &quillmark_core::EditError) -> JsValue {
         quillmark_core::EditError::InvalidFieldName(_) => "InvalidFieldName",
         quillmark_core::EditError::InvalidTagName(_) => "InvalidTagName",
         quillmark_core::EditError::IndexOutOfRange { .. } => "IndexOutOfRange",
+        quillmark_core::EditError::ReferenceCycle(_) => "ReferenceCycle",
+        quillmark_core::EditError::ReferencesTooDeep { .. } => "ReferencesTooDeep",
+        quillmark_core::EditError::InterpolationTooLarge { .. } => "InterpolationTooLarge",
     };
     WasmError::from(format!("[EditError::{}] {}", variant, err)).to_js_value()
 }
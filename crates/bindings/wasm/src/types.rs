@@ -37,6 +37,34 @@ impl From<quillmark_core::OutputFormat> for OutputFormat {
     }
 }
 
+/// PDF/A archival conformance levels for PDF output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum PdfStandard {
+    #[serde(rename = "a-1b")]
+    A1b,
+    #[serde(rename = "a-2b")]
+    A2b,
+    #[serde(rename = "a-2u")]
+    A2u,
+    #[serde(rename = "a-3b")]
+    A3b,
+    #[serde(rename = "a-3u")]
+    A3u,
+}
+
+impl From<PdfStandard> for quillmark_core::PdfStandard {
+    fn from(standard: PdfStandard) -> Self {
+        match standard {
+            PdfStandard::A1b => quillmark_core::PdfStandard::A1b,
+            PdfStandard::A2b => quillmark_core::PdfStandard::A2b,
+            PdfStandard::A2u => quillmark_core::PdfStandard::A2u,
+            PdfStandard::A3b => quillmark_core::PdfStandard::A3b,
+            PdfStandard::A3u => quillmark_core::PdfStandard::A3u,
+        }
+    }
+}
+
 /// Severity levels for diagnostics
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
@@ -120,6 +148,10 @@ pub struct Artifact {
     #[tsify(type = "Uint8Array")]
     pub bytes: Vec<u8>,
     pub mime_type: String,
+    /// 0-based page index for per-page formats (SVG, PNG); absent for
+    /// whole-document formats (PDF, TXT).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
 }
 
 impl Artifact {
@@ -140,6 +172,7 @@ impl From<quillmark_core::Artifact> for Artifact {
             format,
             mime_type: Self::mime_type_for_format(format),
             bytes: artifact.bytes,
+            page: artifact.page,
         }
     }
 }
@@ -259,6 +292,30 @@ pub struct RenderOptions {
     /// `format: "pdf"` yields a `FormatNotSupported` error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pages: Option<Vec<usize>>,
+    /// Maximum number of pages the document may have; larger documents
+    /// throw a `render::too_many_pages` error. `undefined` means no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+    /// Skip the "document body is empty" warning for documents with no
+    /// body text.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_empty_body: bool,
+    /// Throw a `typst::missing_font` error instead of falling back to a
+    /// default font when the plate asks for a font the quill lacks.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_fonts: bool,
+    /// Card types to render; cards of other types are dropped. `undefined`
+    /// renders every card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_cards: Option<Vec<String>>,
+    /// Seed exposed to the plate as `seed` from the helper package, for
+    /// deterministic pseudo-random layout. `undefined` leaves it unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// PDF/A conformance level for PDF output (e.g. `"a-2b"`). `undefined`
+    /// produces a regular PDF.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf_standard: Option<PdfStandard>,
 }
 
 impl Default for RenderOptions {
@@ -267,17 +324,29 @@ impl Default for RenderOptions {
             format: Some(OutputFormat::Pdf),
             ppi: None,
             pages: None,
+            max_pages: None,
+            allow_empty_body: false,
+            strict_fonts: false,
+            include_cards: None,
+            seed: None,
+            pdf_standard: None,
         }
     }
 }
 
 impl From<RenderOptions> for quillmark_core::RenderOptions {
     fn from(opts: RenderOptions) -> Self {
-        Self {
-            output_format: opts.format.map(|f| f.into()),
-            ppi: opts.ppi,
-            pages: opts.pages,
-        }
+        let mut core = Self::default();
+        core.output_format = opts.format.map(|f| f.into());
+        core.ppi = opts.ppi;
+        core.pages = opts.pages;
+        core.max_pages = opts.max_pages;
+        core.allow_empty_body = opts.allow_empty_body;
+        core.strict_fonts = opts.strict_fonts;
+        core.include_cards = opts.include_cards;
+        core.seed = opts.seed;
+        core.pdf_standard = opts.pdf_standard.map(|s| s.into());
+        core
     }
 }
 
@@ -391,6 +460,12 @@ mod tests {
             format: Some(OutputFormat::Pdf),
             ppi: None,
             pages: None,
+            max_pages: None,
+            allow_empty_body: false,
+            strict_fonts: false,
+            include_cards: None,
+            seed: None,
+            pdf_standard: None,
         };
         let json = serde_json::to_string(&options).unwrap();
         assert!(json.contains("\"format\":\"pdf\""));
@@ -19,4 +19,41 @@ pub trait Backend: Send + Sync + std::fmt::Debug {
         source: &QuillSource,
         json_data: &serde_json::Value,
     ) -> Result<RenderSession, RenderError>;
+
+    /// Like [`Backend::open`], for a session that will only be rendered to
+    /// `format`. Backends may skip assets Quill.yaml limits to other formats
+    /// (see [`QuillSource::asset_applies_to`]).
+    ///
+    /// The default ignores `format` and calls [`Backend::open`].
+    fn open_for_format(
+        &self,
+        plate_content: &str,
+        source: &QuillSource,
+        json_data: &serde_json::Value,
+        format: OutputFormat,
+    ) -> Result<RenderSession, RenderError> {
+        let _ = format;
+        self.open(plate_content, source, json_data)
+    }
+
+    /// Quill-relative paths of the files `plate_content` loads (images, data
+    /// files, includes), in first-reference order.
+    ///
+    /// Used to check for missing assets before compiling. The default reports
+    /// none.
+    fn referenced_assets(&self, plate_content: &str) -> Vec<String> {
+        let _ = plate_content;
+        Vec::new()
+    }
+
+    /// Check backend-specific prerequisites of a quill (required files,
+    /// plate file type) when the engine loads it, so misconfiguration fails
+    /// at load time instead of at the first render. Report problems as
+    /// [`RenderError::QuillConfig`].
+    ///
+    /// The default accepts every quill.
+    fn validate_quill(&self, source: &QuillSource) -> Result<(), RenderError> {
+        let _ = source;
+        Ok(())
+    }
 }
@@ -5,7 +5,7 @@
 
 use std::str::FromStr;
 
-use crate::error::ParseError;
+use crate::error::{offset_to_location, ParseError};
 use crate::value::QuillValue;
 use crate::version::QuillReference;
 use crate::Diagnostic;
@@ -14,7 +14,7 @@ use super::fences::{fence_opener_len, find_metadata_blocks};
 use super::frontmatter::{Frontmatter, FrontmatterItem};
 use super::prescan::{prescan_fence_content, NestedComment, PreItem};
 use super::sentinel::extract_sentinels;
-use super::{Card, Document, Sentinel};
+use super::{BodySpans, Card, Document, ParseOptions, Sentinel};
 
 /// Strip exactly one F2 structural separator from the tail of a body slice.
 ///
@@ -29,7 +29,7 @@ use super::{Card, Document, Sentinel};
 /// `trim_body` did) would silently drop content-meaningful trailing
 /// newlines — e.g. a body that ends with a fenced code block's closing
 /// newline.
-fn strip_f2_separator(body: &str) -> &str {
+pub(super) fn strip_f2_separator(body: &str) -> &str {
     if let Some(rest) = body.strip_suffix("\r\n") {
         rest
     } else if let Some(rest) = body.strip_suffix('\n') {
@@ -57,8 +57,9 @@ pub(super) struct MetadataBlock {
 
 /// Creates serde_saphyr Options with security budgets configured.
 ///
-/// Uses MAX_YAML_DEPTH from limits.rs to limit nesting depth at the parser level,
-/// which is more robust than heuristic-based pre-parse checks.
+/// Uses MAX_YAML_DEPTH and MAX_YAML_ALIAS_EXPANSION from limits.rs to limit
+/// nesting depth and alias replay at the parser level, which is more robust
+/// than heuristic-based pre-parse checks.
 fn yaml_parse_options() -> serde_saphyr::Options {
     let budget = serde_saphyr::Budget {
         max_depth: super::limits::MAX_YAML_DEPTH,
@@ -66,6 +67,10 @@ fn yaml_parse_options() -> serde_saphyr::Options {
     };
     serde_saphyr::Options {
         budget: Some(budget),
+        alias_limits: serde_saphyr::options::AliasLimits {
+            max_total_replayed_events: super::limits::MAX_YAML_ALIAS_EXPANSION,
+            ..Default::default()
+        },
         ..Default::default()
     }
 }
@@ -108,7 +113,7 @@ pub(super) fn build_block(
         ) {
             Ok(parsed) => extract_sentinels(parsed, markdown, abs_pos, block_index)?,
             Err(e) => {
-                let line = markdown[..abs_pos].lines().count() + 1;
+                let line = offset_to_location("", markdown, abs_pos).line as usize;
                 return Err(ParseError::YamlErrorWithLocation {
                     message: e.to_string(),
                     line,
@@ -151,7 +156,7 @@ pub(super) fn build_block(
 /// first-fence F1 failure, tailor the message to the actual key found:
 /// a case-insensitive match to `QUILL` is a typo, anything else is a
 /// key-ordering problem.
-fn missing_quill_message(first_fence_issue: Option<(String, usize)>) -> String {
+pub(super) fn missing_quill_message(first_fence_issue: Option<(String, usize)>) -> String {
     match first_fence_issue {
         Some((actual, line)) if actual.eq_ignore_ascii_case("QUILL") => format!(
             "Missing required QUILL field. Found `{}:` at line {} — expected `QUILL:` (uppercase). Change the key to `QUILL` to register this fence as the document frontmatter.",
@@ -165,30 +170,75 @@ fn missing_quill_message(first_fence_issue: Option<(String, usize)>) -> String {
     }
 }
 
+/// The dedicated error for empty / whitespace-only input.
+pub(super) fn empty_input_error() -> ParseError {
+    ParseError::EmptyInput(
+        "Empty markdown input cannot be parsed as a Quillmark Document. \
+         Provide at least a QUILL frontmatter field: `QUILL: <name>`."
+            .to_string(),
+    )
+}
+
 /// Decompose markdown, discarding warnings. Test- and `from_markdown`-facing.
 pub(super) fn decompose(markdown: &str) -> Result<Document, crate::error::ParseError> {
     decompose_with_warnings(markdown).map(|(doc, _)| doc)
 }
 
+/// The body Markdown of a document with every metadata fence removed.
+///
+/// The main body and each card body are kept verbatim and in source order,
+/// joined by a blank line; blank bodies are dropped. Fences are detected
+/// exactly as [`Document::from_markdown`] does, so `---` inside code blocks
+/// stays put and the same parse errors apply.
+pub fn strip_frontmatter(markdown: &str) -> Result<String, ParseError> {
+    let doc = decompose(markdown)?;
+    let bodies: Vec<&str> = std::iter::once(doc.main())
+        .chain(doc.cards())
+        .map(|card| card.body().trim_matches('\n'))
+        .filter(|body| !body.trim().is_empty())
+        .collect();
+    Ok(bodies.join("\n\n"))
+}
+
 /// Decompose markdown into a typed [`Document`], returning any non-fatal warnings
 /// collected during fence scanning.
 pub(super) fn decompose_with_warnings(
     markdown: &str,
 ) -> Result<(Document, Vec<Diagnostic>), crate::error::ParseError> {
+    decompose_with_options(markdown, &ParseOptions::default())
+}
+
+/// Decompose markdown under the given [`ParseOptions`]. In strict mode, a
+/// `CARD:` tag outside `options.allowed_cards` fails with
+/// [`ParseError::UnknownCard`] pointing at that block.
+pub(super) fn decompose_with_options(
+    markdown: &str,
+    options: &ParseOptions,
+) -> Result<(Document, Vec<Diagnostic>), crate::error::ParseError> {
+    decompose_with_spans(markdown, options).map(|(doc, warnings, _)| (doc, warnings))
+}
+
+/// [`decompose_with_options`], also returning where each body sits in
+/// `markdown`.
+pub(super) fn decompose_with_spans(
+    markdown: &str,
+    options: &ParseOptions,
+) -> Result<(Document, Vec<Diagnostic>, BodySpans), crate::error::ParseError> {
     // Strip a leading UTF-8 BOM if present. Editors on Windows (Notepad, some
     // Word exports) prepend `\u{FEFF}` which otherwise defeats F2 because the
     // first line no longer matches `---`.
+    let original_len = markdown.len();
     let markdown = markdown.strip_prefix('\u{FEFF}').unwrap_or(markdown);
+    // Offsets below index the BOM-stripped text; spans are shifted back to
+    // the caller's original input.
+    let bom_len = original_len - markdown.len();
+    let span = |start: usize, body: &str| start + bom_len..start + bom_len + body.len();
 
     // Empty / whitespace-only input gets a tailored message. The default
     // missing-QUILL error reads as if the user supplied a partial document
     // missing only QUILL, which is misleading when there's no document at all.
     if markdown.trim().is_empty() {
-        return Err(crate::error::ParseError::EmptyInput(
-            "Empty markdown input cannot be parsed as a Quillmark Document. \
-             Provide at least a QUILL frontmatter field: `QUILL: <name>`."
-                .to_string(),
-        ));
+        return Err(empty_input_error());
     }
 
     // Check input size limit
@@ -253,10 +303,24 @@ pub(super) fn decompose_with_warnings(
         global_body_raw.to_string()
     };
 
+    let mut spans = BodySpans {
+        main: span(body_start, &global_body),
+        cards: Vec::new(),
+    };
+
     // Parse tagged blocks (CARD blocks) into typed Cards.
     let mut cards: Vec<Card> = Vec::new();
     for (idx, block) in blocks.iter().enumerate() {
         if let Some(ref tag_name) = block.tag {
+            if options.strict && !options.allowed_cards.iter().any(|t| t == tag_name) {
+                return Err(ParseError::UnknownCard {
+                    tag: tag_name.clone(),
+                    line: offset_to_location("", markdown, block.start).line as usize,
+                    block_index: idx,
+                    allowed: options.allowed_cards.clone(),
+                });
+            }
+
             // Build the card's typed frontmatter from pre-scan + parsed YAML.
             let card_frontmatter = build_frontmatter_from_pre_and_parsed(
                 &block.pre_items,
@@ -289,6 +353,7 @@ pub(super) fn decompose_with_warnings(
                 card_body_raw.to_string()
             };
 
+            spans.cards.push(span(card_body_start, &card_body));
             cards.push(Card::new_with_sentinel(
                 Sentinel::Card(tag_name.clone()),
                 card_frontmatter,
@@ -304,7 +369,7 @@ pub(super) fn decompose_with_warnings(
     let main = Card::new_with_sentinel(Sentinel::Main(quill_ref), frontmatter, global_body);
     let doc = Document::from_main_and_cards(main, cards, warnings.clone());
 
-    Ok((doc, warnings))
+    Ok((doc, warnings, spans))
 }
 
 /// Build a [`Frontmatter`] from the pre-scan items and the parsed YAML
@@ -316,7 +381,7 @@ pub(super) fn decompose_with_warnings(
 /// didn't catch (e.g. it used a YAML key form the pre-scan doesn't
 /// recognise — exotic identifier, flow-mapping syntax, etc.) is appended at
 /// the end of the item list in parsed-map order so we never drop values.
-fn build_frontmatter_from_pre_and_parsed(
+pub(super) fn build_frontmatter_from_pre_and_parsed(
     pre_items: &[PreItem],
     pre_nested_comments: &[NestedComment],
     yaml_value: &Option<serde_json::Value>,
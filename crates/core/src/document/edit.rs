@@ -89,6 +89,20 @@ pub enum EditError {
     /// A card index was out of the valid range.
     #[error("index {index} is out of range (len = {len})")]
     IndexOutOfRange { index: usize, len: usize },
+
+    /// `{{ name }}` field references form a cycle; lists the fields in
+    /// reference order, ending with the repeated one.
+    #[error("field references form a cycle: {}", .0.join(" -> "))]
+    ReferenceCycle(Vec<String>),
+
+    /// `{{ name }}` references chain through more than `max` fields; lists
+    /// the chain up to where the limit hit.
+    #[error("field references are nested deeper than {max} levels: {}", .chain.join(" -> "))]
+    ReferencesTooDeep { chain: Vec<String>, max: usize },
+
+    /// `{{ name }}` interpolation produced more than `max` bytes.
+    #[error("field interpolation output exceeds the {max}-byte limit")]
+    InterpolationTooLarge { max: usize },
 }
 
 // ── impl Document ────────────────────────────────────────────────────────────
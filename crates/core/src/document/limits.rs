@@ -8,3 +8,9 @@
 /// Prevents stack overflow from deeply nested YAML structures.
 /// Enforced at the serde-saphyr parser level via [`serde_saphyr::Budget`].
 pub const MAX_YAML_DEPTH: usize = 100;
+
+/// Maximum YAML events replayed through aliases per metadata block (10,000).
+///
+/// Prevents "billion laughs" alias bombs from expanding a small fence into a huge value.
+/// Enforced at the serde-saphyr parser level via [`serde_saphyr::options::AliasLimits`].
+pub const MAX_YAML_ALIAS_EXPANSION: usize = 10_000;
@@ -67,13 +67,18 @@
 //! - Both QUILL and CARD specified in the same block
 //! - Reserved field name usage
 //! - Name collisions
+//! - Unknown `CARD:` tags, in strict mode ([`ParseOptions`])
 //!
 //! See [PARSE.md](https://github.com/nibsbin/quillmark/blob/main/designs/PARSE.md) for
 //! comprehensive documentation of the Extended YAML Metadata Standard.
 
+use std::collections::HashMap;
+use std::ops::Range;
+
 use crate::error::ParseError;
+use crate::value::FieldType;
 use crate::version::QuillReference;
-use crate::Diagnostic;
+use crate::{Diagnostic, Severity};
 
 pub mod assemble;
 pub mod edit;
@@ -82,10 +87,15 @@ pub mod fences;
 pub mod frontmatter;
 pub mod limits;
 pub mod prescan;
+pub mod references;
 pub mod sentinel;
+pub mod stream;
 
+pub use assemble::strip_frontmatter;
 pub use edit::EditError;
 pub use frontmatter::{Frontmatter, FrontmatterItem};
+pub use references::{interpolate_fields, interpolate_fields_with_limit};
+pub use stream::{decompose_blocks, CardStream};
 
 // Re-export the sentinel type (defined below in this module file).
 // `Sentinel` is exported at the crate root via `lib.rs`.
@@ -95,12 +105,136 @@ mod tests;
 
 /// Parse result carrying both the parsed document and any non-fatal warnings
 /// (e.g. near-miss sentinel lints emitted per spec §4.2).
+///
+/// Non-exhaustive so parse results can grow new fields; build one outside
+/// this crate with [`ParseOutput::new`].
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct ParseOutput {
     /// The successfully parsed document.
     pub document: Document,
     /// Non-fatal warnings collected during parsing.
     pub warnings: Vec<Diagnostic>,
+    /// Where each body sits in the parsed markdown.
+    pub body_spans: BodySpans,
+}
+
+impl ParseOutput {
+    /// A parse result for `document` with empty [`BodySpans`], e.g. for a
+    /// document that was built rather than parsed.
+    pub fn new(document: Document, warnings: Vec<Diagnostic>) -> Self {
+        Self {
+            document,
+            warnings,
+            body_spans: BodySpans::default(),
+        }
+    }
+
+    /// Replace the body spans.
+    pub fn with_body_spans(mut self, body_spans: BodySpans) -> Self {
+        self.body_spans = body_spans;
+        self
+    }
+}
+
+/// Byte ranges of the main and card bodies in the markdown they were parsed
+/// from, so diagnostics about a body can point back at its source.
+///
+/// Ranges index the input as given (a leading BOM counts) and cover exactly
+/// the stored body text: `&markdown[range]` equals the body unless
+/// [`ParseOptions::normalize_unicode`] rewrote it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BodySpans {
+    /// Range of the main card's body.
+    pub main: Range<usize>,
+    /// Range of each card's body, parallel to [`Document::cards`].
+    pub cards: Vec<Range<usize>>,
+}
+
+/// Options controlling how [`Document::from_markdown_with_options`] parses.
+///
+/// The default is lenient: any valid `CARD:` tag is accepted. In strict mode
+/// a `CARD:` tag not listed in `allowed_cards` is rejected with
+/// [`ParseError::UnknownCard`], so a typo'd tag fails at parse time instead
+/// of producing a card nothing reads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Reject `CARD:` tags outside `allowed_cards`.
+    pub strict: bool,
+    /// Card tags accepted in strict mode (typically the quill's `card_types`).
+    /// Ignored when `strict` is `false`.
+    pub allowed_cards: Vec<String>,
+    /// NFC-normalize top-level field names and bodies, so a key authored as
+    /// `cafe\u{301}` is looked up as `café`. Off by default because it can
+    /// change the bytes of the body; field values pass through verbatim.
+    pub normalize_unicode: bool,
+    /// Names a field should not take, such as helpers a plate uses next to
+    /// the document's fields. A field with one of these names, or with a
+    /// reserved name in other case (`body` for `BODY`), gets a
+    /// `parse::shadowed_name` warning. Empty (the default) checks nothing.
+    pub shadowed_names: Vec<String>,
+}
+
+impl ParseOptions {
+    /// Strict options accepting exactly the given card tags.
+    pub fn strict<I, S>(allowed_cards: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            strict: true,
+            allowed_cards: allowed_cards.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// What changed between two versions of a document, from
+/// [`Document::diff_cards`].
+///
+/// Cards are compared by position, so inserting a card in the middle marks
+/// it and every card after it as changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardDiff {
+    /// The main card's fields or body changed.
+    pub main_changed: bool,
+    /// Indices, in the newer document, of cards that differ from the card at
+    /// the same position in the older one or have no counterpart there.
+    pub changed: Vec<usize>,
+    /// Trailing cards of the older document with no counterpart in the
+    /// newer one.
+    pub removed: usize,
+}
+
+impl CardDiff {
+    /// Returns `true` when the two documents have identical content.
+    pub fn is_empty(&self) -> bool {
+        !self.main_changed && self.changed.is_empty() && self.removed == 0
+    }
+}
+
+/// Text encoding for [`Document::dump_context`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Pretty-printed JSON.
+    Json,
+    /// Block-style YAML.
+    Yaml,
+}
+
+impl DumpFormat {
+    /// Pretty-print `value` in this format.
+    pub fn dump(self, value: &serde_json::Value) -> String {
+        match self {
+            DumpFormat::Json => {
+                serde_json::to_string_pretty(value).expect("JSON values always serialize")
+            }
+            DumpFormat::Yaml => {
+                serde_saphyr::to_string(value).expect("JSON values always serialize as YAML")
+            }
+        }
+    }
 }
 
 /// Discriminator for a [`Card`]'s metadata fence.
@@ -200,11 +334,35 @@ impl Card {
         &self.body
     }
 
+    /// The body as sanitized HTML, for previews; see
+    /// [`crate::html::markdown_to_html`].
+    pub fn body_html(&self) -> String {
+        crate::html::markdown_to_html(&self.body)
+    }
+
     /// Returns `true` if this is the document entry (main) card.
     pub fn is_main(&self) -> bool {
         self.sentinel.is_main()
     }
 
+    /// Deserialize this card's fields into `T`, with the body available as
+    /// a `body` field.
+    ///
+    /// A frontmatter field named `body` takes precedence over the card body.
+    pub fn deserialize_fields<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<T, serde_json::Error> {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "body".to_string(),
+            serde_json::Value::String(self.body.clone()),
+        );
+        for (key, value) in self.frontmatter.to_index_map() {
+            map.insert(key, value.into_json());
+        }
+        serde_json::from_value(serde_json::Value::Object(map))
+    }
+
     /// Replace this card's sentinel. Internal helper; public mutators
     /// ([`Document::set_quill_ref`], the parser) call this.
     pub(crate) fn replace_sentinel(&mut self, sentinel: Sentinel) {
@@ -215,6 +373,45 @@ impl Card {
     pub(crate) fn overwrite_body(&mut self, body: String) {
         self.body = body;
     }
+
+    /// NFC-normalize top-level field names and the body, for
+    /// [`ParseOptions::normalize_unicode`]. When two keys normalize to the
+    /// same name the later value wins, as for duplicate YAML keys.
+    fn normalize_unicode(&mut self) {
+        use unicode_normalization::UnicodeNormalization;
+
+        let mut items: Vec<FrontmatterItem> = Vec::with_capacity(self.frontmatter.items().len());
+        for item in self.frontmatter.items() {
+            match item {
+                FrontmatterItem::Field { key, value, fill } => {
+                    let key = crate::normalize::normalize_field_name(key);
+                    let existing = items.iter_mut().find_map(|i| match i {
+                        FrontmatterItem::Field {
+                            key: k,
+                            value: v,
+                            fill: f,
+                        } if *k == key => Some((v, f)),
+                        _ => None,
+                    });
+                    match existing {
+                        Some((v, f)) => {
+                            *v = value.clone();
+                            *f = *fill;
+                        }
+                        None => items.push(FrontmatterItem::Field {
+                            key,
+                            value: value.clone(),
+                            fill: *fill,
+                        }),
+                    }
+                }
+                comment => items.push(comment.clone()),
+            }
+        }
+        self.frontmatter =
+            Frontmatter::from_items_with_nested(items, self.frontmatter.nested_comments().to_vec());
+        self.body = self.body.nfc().collect();
+    }
 }
 
 /// A fully-parsed, typed in-memory Quillmark document.
@@ -274,8 +471,66 @@ impl Document {
 
     /// Parse a Quillmark Markdown document, returning warnings alongside the document.
     pub fn from_markdown_with_warnings(markdown: &str) -> Result<ParseOutput, ParseError> {
-        assemble::decompose_with_warnings(markdown)
-            .map(|(document, warnings)| ParseOutput { document, warnings })
+        Self::from_markdown_with_options(markdown, &ParseOptions::default())
+    }
+
+    /// Parse a Quillmark Markdown document under the given [`ParseOptions`],
+    /// returning warnings alongside the document.
+    pub fn from_markdown_with_options(
+        markdown: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseOutput, ParseError> {
+        let (mut document, mut warnings, body_spans) =
+            assemble::decompose_with_spans(markdown, options)?;
+        if options.normalize_unicode {
+            document.main.normalize_unicode();
+            for card in &mut document.cards {
+                card.normalize_unicode();
+            }
+        }
+        if !options.shadowed_names.is_empty() {
+            let shadow_warnings = document.shadowed_field_warnings(&options.shadowed_names);
+            document.warnings.extend(shadow_warnings.iter().cloned());
+            warnings.extend(shadow_warnings);
+        }
+        Ok(ParseOutput::new(document, warnings).with_body_spans(body_spans))
+    }
+
+    /// One warning per field, in the main card or any card, whose name is in
+    /// `shadowed` or matches a reserved name only up to case.
+    fn shadowed_field_warnings(&self, shadowed: &[String]) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+        for card in std::iter::once(&self.main).chain(&self.cards) {
+            for key in card.frontmatter.keys() {
+                let reserved = edit::RESERVED_NAMES
+                    .iter()
+                    .find(|name| name.eq_ignore_ascii_case(key));
+                let clash = if shadowed.contains(key) {
+                    "it shadows a name the plate uses".to_string()
+                } else if let Some(reserved) = reserved {
+                    format!("it differs from the reserved `{}` only in case", reserved)
+                } else {
+                    continue;
+                };
+                let location = if card.is_main() {
+                    "frontmatter".to_string()
+                } else {
+                    format!("card '{}'", card.tag())
+                };
+                warnings.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        format!("Field `{}` in {} is ambiguous: {}", key, location, clash),
+                    )
+                    .with_code("parse::shadowed_name".to_string())
+                    .with_hint(format!(
+                        "Rename `{}` to a name that is not already taken",
+                        key
+                    )),
+                );
+            }
+        }
+        warnings
     }
 
     // ── Accessors ──────────────────────────────────────────────────────────────
@@ -285,6 +540,14 @@ impl Document {
         &self.main
     }
 
+    /// Deserialize the main card's fields (and body, as `body`) into `T`.
+    /// See [`Card::deserialize_fields`].
+    pub fn deserialize_fields<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<T, serde_json::Error> {
+        self.main.deserialize_fields()
+    }
+
     /// Mutable access to the main card.
     pub fn main_mut(&mut self) -> &mut Card {
         &mut self.main
@@ -306,6 +569,21 @@ impl Document {
         &self.cards
     }
 
+    /// Compare this document with a newer version of it, card by card.
+    pub fn diff_cards(&self, newer: &Document) -> CardDiff {
+        CardDiff {
+            main_changed: self.main != newer.main,
+            changed: newer
+                .cards
+                .iter()
+                .enumerate()
+                .filter(|(i, card)| self.cards.get(*i) != Some(*card))
+                .map(|(i, _)| i)
+                .collect(),
+            removed: self.cards.len().saturating_sub(newer.cards.len()),
+        }
+    }
+
     /// Mutable access to the composable cards slice.
     pub fn cards_mut(&mut self) -> &mut [Card] {
         &mut self.cards
@@ -323,6 +601,19 @@ impl Document {
         &self.warnings
     }
 
+    /// Inferred type of each main-card frontmatter field, plus `BODY`
+    /// (always [`FieldType::String`]).
+    pub fn field_types(&self) -> HashMap<String, FieldType> {
+        let mut types: HashMap<String, FieldType> = self
+            .main
+            .frontmatter
+            .iter()
+            .map(|(key, value)| (key.clone(), value.field_type()))
+            .collect();
+        types.insert("BODY".to_string(), FieldType::String);
+        types
+    }
+
     // ── Wire format ────────────────────────────────────────────────────────────
 
     /// Serialize this document to the JSON shape expected by backend plates.
@@ -388,4 +679,84 @@ impl Document {
 
         serde_json::Value::Object(map)
     }
+
+    /// The document's plate data ([`Document::to_plate_json`]) as pretty
+    /// JSON or YAML, for inspecting what a plate will receive.
+    ///
+    /// This is the data as authored; a quill's `compile_data` additionally
+    /// applies coercion and schema defaults.
+    pub fn dump_context(&self, format: DumpFormat) -> String {
+        format.dump(&self.to_plate_json())
+    }
+
+    /// Rebuild a `Document` from the wire shape produced by
+    /// [`Document::to_plate_json`], e.g. after shipping it across a process
+    /// boundary.
+    ///
+    /// Field values, bodies, card tags, and the quill reference round-trip
+    /// exactly. Frontmatter comments and `!fill` markers are not part of the
+    /// wire format and are not restored — use Markdown for a fully lossless
+    /// transport. Field names and card tags are validated as on parse.
+    pub fn from_plate_json(json: &serde_json::Value) -> Result<Self, ParseError> {
+        fn invalid(msg: String) -> ParseError {
+            ParseError::InvalidStructure(msg)
+        }
+
+        fn card_from_object(
+            sentinel: Sentinel,
+            obj: &serde_json::Map<String, serde_json::Value>,
+            sentinel_key: &str,
+        ) -> Result<Card, ParseError> {
+            let body = match obj.get("BODY") {
+                None => String::new(),
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(_) => return Err(invalid("`BODY` must be a string".to_string())),
+            };
+            let mut card = Card::new_with_sentinel(sentinel, Frontmatter::new(), body);
+            for (key, value) in obj {
+                if key == sentinel_key || key == "BODY" || key == "CARDS" {
+                    continue;
+                }
+                card.set_field(key, crate::value::QuillValue::from_json(value.clone()))
+                    .map_err(|e| invalid(e.to_string()))?;
+            }
+            Ok(card)
+        }
+
+        let obj = json
+            .as_object()
+            .ok_or_else(|| invalid("plate JSON must be an object".to_string()))?;
+        let quill_tag = obj
+            .get("QUILL")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ParseError::MissingQuillField("Missing `QUILL` string".to_string()))?;
+        let quill_ref = quill_tag
+            .parse::<QuillReference>()
+            .map_err(|e| invalid(format!("Invalid QUILL tag '{}': {}", quill_tag, e)))?;
+        let main = card_from_object(Sentinel::Main(quill_ref), obj, "QUILL")?;
+
+        let mut cards = Vec::new();
+        if let Some(value) = obj.get("CARDS") {
+            let items = value
+                .as_array()
+                .ok_or_else(|| invalid("`CARDS` must be an array".to_string()))?;
+            for (index, item) in items.iter().enumerate() {
+                let card_obj = item
+                    .as_object()
+                    .ok_or_else(|| invalid(format!("CARDS[{}] must be an object", index)))?;
+                let tag = card_obj
+                    .get("CARD")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| invalid(format!("CARDS[{}] is missing a `CARD` tag", index)))?;
+                let template = Card::new(tag).map_err(|e| invalid(e.to_string()))?;
+                cards.push(card_from_object(
+                    template.sentinel().clone(),
+                    card_obj,
+                    "CARD",
+                )?);
+            }
+        }
+
+        Ok(Self::from_main_and_cards(main, cards, Vec::new()))
+    }
 }
@@ -0,0 +1,197 @@
+//! `{{ name }}` field references inside frontmatter string values.
+//!
+//! [`interpolate_fields`] is the single substitution rule, shared by body
+//! templates in the engine and by [`Document::resolve_references`].
+
+use indexmap::IndexMap;
+
+use super::edit::EditError;
+use super::{Card, Document};
+use crate::error::{MAX_EXPANSION_DEPTH, MAX_INTERPOLATED_SIZE};
+use crate::value::QuillValue;
+
+/// Replace `{{ name }}` placeholders in `template` with field values.
+///
+/// Strings are inserted as-is, null as nothing, and other values as JSON.
+/// Placeholders naming an absent field are left in place so a missing
+/// value is visible in the output. Output larger than
+/// [`MAX_INTERPOLATED_SIZE`] fails with [`EditError::InterpolationTooLarge`].
+pub fn interpolate_fields(
+    template: &str,
+    fields: &IndexMap<String, QuillValue>,
+) -> Result<String, EditError> {
+    interpolate_fields_with_limit(template, fields, MAX_INTERPOLATED_SIZE)
+}
+
+/// [`interpolate_fields`] with a caller-chosen output cap of `max` bytes.
+pub fn interpolate_fields_with_limit(
+    template: &str,
+    fields: &IndexMap<String, QuillValue>,
+    max: usize,
+) -> Result<String, EditError> {
+    interpolate(template, |name| fields.get(name), max)
+        .ok_or(EditError::InterpolationTooLarge { max })
+}
+
+/// Substitute placeholders using `lookup`, or `None` when the output would
+/// exceed `max` bytes.
+fn interpolate<'a>(
+    template: &str,
+    lookup: impl Fn(&str) -> Option<&'a QuillValue>,
+    max: usize,
+) -> Option<String> {
+    if template.len() > max {
+        return None;
+    }
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        out.push_str(&rest[..start]);
+        match lookup(name).map(QuillValue::as_json) {
+            Some(serde_json::Value::String(s)) => out.push_str(s),
+            Some(serde_json::Value::Null) => {}
+            Some(value) => out.push_str(&value.to_string()),
+            None => out.push_str(&rest[start..start + len + 4]),
+        }
+        rest = &rest[start + len + 4..];
+        if out.len() + rest.len() > max {
+            return None;
+        }
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// Field names referenced by `{{ name }}` placeholders in `template`.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + 2 + len].trim());
+        rest = &rest[start + len + 4..];
+    }
+    names
+}
+
+impl Document {
+    /// Expand `{{ name }}` references in top-level string fields, in place.
+    ///
+    /// Main-card fields reference other main-card fields. Card fields
+    /// reference the card's own fields first, then the (already resolved)
+    /// main-card fields. References may chain up to
+    /// [`MAX_EXPANSION_DEPTH`] fields deep; a cycle fails with
+    /// [`EditError::ReferenceCycle`] and a longer chain with
+    /// [`EditError::ReferencesTooDeep`], leaving the document unchanged.
+    /// Expanded fields share one [`MAX_INTERPOLATED_SIZE`] budget across the
+    /// whole document; exceeding it fails with
+    /// [`EditError::InterpolationTooLarge`].
+    /// Non-string values, nested values, and bodies are not touched.
+    pub fn resolve_references(&mut self) -> Result<(), EditError> {
+        let mut budget = MAX_INTERPOLATED_SIZE;
+        let globals = resolved_fields(self.main(), &IndexMap::new(), &mut budget)?;
+        let cards = self
+            .cards()
+            .iter()
+            .map(|card| resolved_fields(card, &globals, &mut budget))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        apply(self.main_mut(), globals);
+        for (card, fields) in self.cards_mut().iter_mut().zip(cards) {
+            apply(card, fields);
+        }
+        Ok(())
+    }
+}
+
+/// The card's fields with references resolved against its own fields and
+/// then `globals`. Contains only the card's own keys. Each expanded field is
+/// charged to `budget`.
+fn resolved_fields(
+    card: &Card,
+    globals: &IndexMap<String, QuillValue>,
+    budget: &mut usize,
+) -> Result<IndexMap<String, QuillValue>, EditError> {
+    let own = card.frontmatter().to_index_map();
+    let mut scope = own.clone();
+
+    let mut done: Vec<String> = Vec::new();
+    for name in own.keys() {
+        let mut path = Vec::new();
+        resolve(
+            name, &own, globals, &mut scope, &mut done, &mut path, budget,
+        )?;
+    }
+    Ok(scope)
+}
+
+/// Resolve `name` (one of `own`) into `scope`, resolving the own fields it
+/// references first. `path` is the chain being resolved, for cycle reports.
+fn resolve(
+    name: &str,
+    own: &IndexMap<String, QuillValue>,
+    globals: &IndexMap<String, QuillValue>,
+    scope: &mut IndexMap<String, QuillValue>,
+    done: &mut Vec<String>,
+    path: &mut Vec<String>,
+    budget: &mut usize,
+) -> Result<(), EditError> {
+    if done.iter().any(|d| d == name) {
+        return Ok(());
+    }
+    if let Some(pos) = path.iter().position(|p| p == name) {
+        let mut cycle = path[pos..].to_vec();
+        cycle.push(name.to_string());
+        return Err(EditError::ReferenceCycle(cycle));
+    }
+    let Some(template) = own.get(name).and_then(QuillValue::as_str) else {
+        done.push(name.to_string());
+        return Ok(());
+    };
+
+    path.push(name.to_string());
+    if path.len() > MAX_EXPANSION_DEPTH {
+        return Err(EditError::ReferencesTooDeep {
+            chain: path.clone(),
+            max: MAX_EXPANSION_DEPTH,
+        });
+    }
+    let deps = placeholders(template);
+    for dep in &deps {
+        if own.contains_key(*dep) {
+            resolve(dep, own, globals, scope, done, path, budget)?;
+        }
+    }
+    path.pop();
+
+    if deps.is_empty() {
+        done.push(name.to_string());
+        return Ok(());
+    }
+    let value = interpolate(
+        template,
+        |key| scope.get(key).or_else(|| globals.get(key)),
+        *budget,
+    )
+    .ok_or(EditError::InterpolationTooLarge {
+        max: MAX_INTERPOLATED_SIZE,
+    })?;
+    *budget -= value.len();
+    scope.insert(name.to_string(), QuillValue::from_json(value.into()));
+    done.push(name.to_string());
+    Ok(())
+}
+
+fn apply(card: &mut Card, fields: IndexMap<String, QuillValue>) {
+    for (key, value) in fields {
+        if card.frontmatter().get(&key) != Some(&value) {
+            card.frontmatter_mut().insert(key, value);
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! Streaming decomposition of large Quillmark Markdown documents.
+//!
+//! [`decompose_blocks`] reads a document line by line and yields the main card
+//! followed by each `CARD` card as soon as its body is complete, so the whole
+//! input never has to be held in memory. It applies the same F1/F2 fence rules
+//! and code-fence shielding as the eager parser.
+//!
+//! Size limits are enforced per block rather than over the whole input: each
+//! card (fence plus body) is capped at [`MAX_INPUT_SIZE`] and each fence at
+//! [`MAX_YAML_SIZE`]. The document-wide [`MAX_CARD_COUNT`] does not apply.
+//!
+//! [`MAX_INPUT_SIZE`]: crate::error::MAX_INPUT_SIZE
+//! [`MAX_YAML_SIZE`]: crate::error::MAX_YAML_SIZE
+//! [`MAX_CARD_COUNT`]: crate::error::MAX_CARD_COUNT
+
+use std::collections::VecDeque;
+use std::io::{BufRead, Read};
+use std::str::FromStr;
+
+use crate::error::ParseError;
+use crate::version::QuillReference;
+use crate::{Diagnostic, Severity};
+
+use super::assemble::{
+    build_block, build_frontmatter_from_pre_and_parsed, empty_input_error, missing_quill_message,
+    strip_f2_separator,
+};
+use super::fences::{code_fence_on_line, is_fence_marker_line};
+use super::sentinel::first_content_key;
+use super::{Card, Sentinel};
+
+/// Stream the cards of a Quillmark Markdown document from `reader`.
+///
+/// The first item is the main (`QUILL`) card; every following item is a
+/// composable (`CARD`) card, in source order. Each card is yielded once the
+/// next metadata fence (or EOF) has been seen. Iteration stops after the
+/// first error.
+pub fn decompose_blocks<R: BufRead>(reader: R) -> CardStream<R> {
+    CardStream {
+        reader,
+        pending: VecDeque::new(),
+        next_line: 0,
+        segment: None,
+        open_code_fence: None,
+        prev_blank: true,
+        seen_main: false,
+        saw_content: false,
+        emitted: 0,
+        first_fence_issue: None,
+        warnings: Vec::new(),
+        done: false,
+    }
+}
+
+/// Iterator over the cards of a streamed document. Created by [`decompose_blocks`].
+pub struct CardStream<R> {
+    reader: R,
+    /// Lines read ahead while probing a fence opener that turned out not to be
+    /// a metadata fence; they are re-scanned before reading further.
+    pending: VecDeque<(usize, String)>,
+    next_line: usize,
+    /// The card currently being accumulated (fence plus body so far).
+    segment: Option<Segment>,
+    /// (char, min_run_len, opener_line_index)
+    open_code_fence: Option<(u8, usize, usize)>,
+    prev_blank: bool,
+    seen_main: bool,
+    saw_content: bool,
+    emitted: usize,
+    first_fence_issue: Option<(String, usize)>,
+    warnings: Vec<Diagnostic>,
+    done: bool,
+}
+
+/// Source text of one card: the metadata fence followed by its body.
+struct Segment {
+    text: String,
+    closer_start: usize,
+    fence_end: usize,
+    start_line: usize,
+}
+
+/// Line text without its trailing line ending.
+fn line_text(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+impl<R: BufRead> CardStream<R> {
+    /// Non-fatal warnings collected so far (near-miss sentinels, unsupported
+    /// tags, unclosed code blocks). Complete once the iterator is exhausted.
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    fn next_raw_line(&mut self) -> Result<Option<(usize, String)>, ParseError> {
+        if let Some(item) = self.pending.pop_front() {
+            return Ok(Some(item));
+        }
+        // Cap the read so a stream without newlines cannot grow one line
+        // without bound before the per-card limits are checked.
+        let max = crate::error::MAX_INPUT_SIZE;
+        let mut bytes = Vec::new();
+        let read = self
+            .reader
+            .by_ref()
+            .take(max as u64 + 1)
+            .read_until(b'\n', &mut bytes)
+            .map_err(|e| ParseError::Other(format!("Failed to read markdown input: {}", e)))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if bytes.len() > max {
+            return Err(ParseError::InputTooLarge {
+                size: bytes.len(),
+                max,
+            });
+        }
+        let mut line = String::from_utf8(bytes).map_err(|e| {
+            ParseError::Other(format!("Failed to read markdown input: {}", e.utf8_error()))
+        })?;
+        if self.next_line == 0 {
+            // Same leading-BOM tolerance as the eager parser.
+            if let Some(rest) = line.strip_prefix('\u{FEFF}') {
+                line = rest.to_string();
+            }
+        }
+        if !line.trim().is_empty() {
+            self.saw_content = true;
+        }
+        let line_no = self.next_line;
+        self.next_line += 1;
+        Ok(Some((line_no, line)))
+    }
+
+    fn advance(&mut self) -> Result<Option<Card>, ParseError> {
+        loop {
+            let Some((line_no, line)) = self.next_raw_line()? else {
+                return self.finish();
+            };
+            if let Some(card) = self.feed(line_no, line)? {
+                return Ok(Some(card));
+            }
+        }
+    }
+
+    fn feed(&mut self, line_no: usize, line: String) -> Result<Option<Card>, ParseError> {
+        let text = line_text(&line);
+
+        if let Some((ch, min, _opener)) = self.open_code_fence {
+            if let Some((_, _, true)) = code_fence_on_line(text, Some((ch, min))) {
+                self.open_code_fence = None;
+            }
+            self.push_body(line)?;
+            return Ok(None);
+        }
+        if let Some((ch, run_len, _)) = code_fence_on_line(text, None) {
+            self.open_code_fence = Some((ch, run_len, line_no));
+            self.push_body(line)?;
+            return Ok(None);
+        }
+        if !is_fence_marker_line(text) || !self.prev_blank {
+            self.push_body(line)?;
+            return Ok(None);
+        }
+        self.try_open_fence(line_no, line)
+    }
+
+    /// Probe a `---` candidate opener. Recognised fences close the current
+    /// segment (returning its card) and start a new one; anything else is
+    /// body text and the lines read ahead are re-queued.
+    fn try_open_fence(
+        &mut self,
+        line_no: usize,
+        opener: String,
+    ) -> Result<Option<Card>, ParseError> {
+        let expected = if self.seen_main { "CARD" } else { "QUILL" };
+
+        // Read up to the first content line (or the closer) to decide F1.
+        let mut ahead: Vec<(usize, String)> = Vec::new();
+        let mut key: Option<String> = None;
+        while let Some((n, l)) = self.next_raw_line()? {
+            let t = line_text(&l);
+            let is_closer = is_fence_marker_line(t);
+            let is_content = !t.trim().is_empty() && !t.trim_start().starts_with('#');
+            if !is_closer && is_content {
+                key = first_content_key(t).map(str::to_string);
+            }
+            ahead.push((n, l));
+            if is_closer || is_content {
+                break;
+            }
+        }
+
+        if key.as_deref() != Some(expected) {
+            if let Some(actual) = key {
+                self.warnings.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        format!(
+                            "Near-miss metadata sentinel `{}:` at line {} — expected `{}:`. This `---/---` pair is treated as literal Markdown; if you intended a metadata fence, change the key to `{}`.",
+                            actual, line_no + 1, expected, expected
+                        ),
+                    )
+                    .with_code("parse::near_miss_sentinel".to_string()),
+                );
+                if !self.seen_main && self.first_fence_issue.is_none() {
+                    self.first_fence_issue = Some((actual, line_no + 1));
+                }
+            }
+            self.push_body(opener)?;
+            for item in ahead.into_iter().rev() {
+                self.pending.push_front(item);
+            }
+            return Ok(None);
+        }
+
+        // F1 passed — read through the closer.
+        let mut yaml_len: usize = ahead.iter().map(|(_, l)| l.len()).sum();
+        loop {
+            let Some((n, l)) = self.next_raw_line()? else {
+                return Err(ParseError::InvalidStructure(
+                    "Metadata block started but not closed with ---".to_string(),
+                ));
+            };
+            let is_closer = is_fence_marker_line(line_text(&l));
+            if !is_closer {
+                yaml_len += l.len();
+            }
+            ahead.push((n, l));
+            if is_closer {
+                break;
+            }
+            if yaml_len > crate::error::MAX_YAML_SIZE {
+                return Err(ParseError::InputTooLarge {
+                    size: yaml_len,
+                    max: crate::error::MAX_YAML_SIZE,
+                });
+            }
+        }
+
+        let mut text = opener;
+        let mut closer_start = text.len();
+        let last = ahead.len() - 1;
+        for (i, (_, l)) in ahead.into_iter().enumerate() {
+            if i == last {
+                closer_start = text.len();
+            }
+            text.push_str(&l);
+        }
+        let fence_end = text.len();
+
+        self.prev_blank = false;
+        self.seen_main = true;
+        let finished = self.segment.replace(Segment {
+            text,
+            closer_start,
+            fence_end,
+            start_line: line_no,
+        });
+        match finished {
+            Some(segment) => self.build_card(segment, true).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn push_body(&mut self, line: String) -> Result<(), ParseError> {
+        self.prev_blank = line.chars().all(char::is_whitespace);
+        // Text ahead of the QUILL fence is not part of any card.
+        let Some(segment) = self.segment.as_mut() else {
+            return Ok(());
+        };
+        segment.text.push_str(&line);
+        if segment.text.len() > crate::error::MAX_INPUT_SIZE {
+            return Err(ParseError::InputTooLarge {
+                size: segment.text.len(),
+                max: crate::error::MAX_INPUT_SIZE,
+            });
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<Option<Card>, ParseError> {
+        if !self.seen_main {
+            if !self.saw_content {
+                return Err(empty_input_error());
+            }
+            return Err(ParseError::MissingQuillField(missing_quill_message(
+                self.first_fence_issue.take(),
+            )));
+        }
+        if let Some((_, _, opener_line)) = self.open_code_fence.take() {
+            self.warnings.push(
+                Diagnostic::new(
+                    Severity::Warning,
+                    format!(
+                        "Unclosed fenced code block opened at line {} — end-of-document reached without a matching closing fence. Any `---/---` pairs after this line were treated as code and not parsed as metadata fences.",
+                        opener_line + 1
+                    ),
+                )
+                .with_code("parse::unclosed_code_block".to_string()),
+            );
+        }
+        match self.segment.take() {
+            Some(segment) => self.build_card(segment, false).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn build_card(
+        &mut self,
+        segment: Segment,
+        followed_by_fence: bool,
+    ) -> Result<Card, ParseError> {
+        let block_index = self.emitted;
+        let block = build_block(
+            &segment.text,
+            0,
+            segment.closer_start,
+            segment.fence_end,
+            block_index,
+        )
+        .map_err(|e| match e {
+            // `build_block` counts lines within the segment; report them
+            // relative to the whole stream.
+            ParseError::YamlErrorWithLocation {
+                message,
+                line,
+                block_index,
+            } => ParseError::YamlErrorWithLocation {
+                message,
+                line: line + segment.start_line,
+                block_index,
+            },
+            other => other,
+        })?;
+
+        let frontmatter = build_frontmatter_from_pre_and_parsed(
+            &block.pre_items,
+            &block.pre_nested_comments,
+            &block.yaml_value,
+        );
+        let sentinel = match (block.quill_ref, block.tag) {
+            (Some(quill_tag), _) => {
+                let quill_ref = QuillReference::from_str(&quill_tag).map_err(|e| {
+                    ParseError::InvalidStructure(format!(
+                        "Invalid QUILL tag '{}': {}",
+                        quill_tag, e
+                    ))
+                })?;
+                Sentinel::Main(quill_ref)
+            }
+            (None, Some(tag_name)) => Sentinel::Card(tag_name),
+            (None, None) => {
+                return Err(ParseError::InvalidStructure(
+                    "Metadata block is missing its QUILL or CARD sentinel".to_string(),
+                ));
+            }
+        };
+        let frontmatter = frontmatter.map_err(|e| match (&sentinel, e) {
+            (Sentinel::Card(tag_name), ParseError::InvalidStructure(msg)) => {
+                ParseError::InvalidStructure(format!(
+                    "Invalid YAML in card block '{}': {}",
+                    tag_name, msg
+                ))
+            }
+            (_, other) => other,
+        })?;
+        self.warnings.extend(block.pre_warnings);
+
+        let body_raw = &segment.text[segment.fence_end..];
+        let body = if followed_by_fence {
+            strip_f2_separator(body_raw)
+        } else {
+            body_raw
+        };
+
+        self.emitted += 1;
+        Ok(Card::new_with_sentinel(
+            sentinel,
+            frontmatter,
+            body.to_string(),
+        ))
+    }
+}
+
+impl<R: BufRead> Iterator for CardStream<R> {
+    type Item = Result<Card, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.advance() {
+            Ok(Some(card)) => Some(Ok(card)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
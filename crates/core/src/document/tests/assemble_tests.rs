@@ -1246,6 +1246,41 @@ fn test_yaml_depth_limit() {
     );
 }
 
+#[test]
+fn test_yaml_alias_bomb_is_rejected() {
+    // Nine levels of ten-fold aliasing would expand to 10^9 scalars.
+    let mut yaml = String::from(
+        "QUILL: test_quill\nl0: &l0 [lol, lol, lol, lol, lol, lol, lol, lol, lol, lol]\n",
+    );
+    for level in 1..9 {
+        let refs = vec![format!("*l{}", level - 1); 10].join(", ");
+        yaml.push_str(&format!("l{level}: &l{level} [{refs}]\n"));
+    }
+    let markdown = format!("---\n{}---\n\nBody", yaml);
+
+    let err = decompose(&markdown).unwrap_err();
+    assert!(
+        matches!(err, crate::error::ParseError::YamlErrorWithLocation { .. }),
+        "expected a YAML error, got: {:?}",
+        err
+    );
+    assert!(
+        err.to_string().to_lowercase().contains("alias"),
+        "expected an alias-limit error, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_yaml_aliases_within_limit() {
+    let markdown = "---\nQUILL: test_quill\nbase: &base {rank: Capt, unit: 1 FW}\nfrom: *base\nto: *base\n---\n\nBody";
+    let doc = decompose(markdown).unwrap();
+    assert_eq!(
+        doc.main().frontmatter().get("to"),
+        doc.main().frontmatter().get("base")
+    );
+}
+
 #[test]
 fn test_yaml_depth_within_limit() {
     let markdown = r#"---
@@ -1512,6 +1547,56 @@ fn test_unicode_in_yaml_keys() {
     );
 }
 
+#[test]
+fn test_normalize_unicode_unifies_nfc_and_nfd_keys() {
+    let nfd = "---\nQUILL: test_quill\ncafe\u{301}: latte\n---\n\nCafe\u{301} body.\n\n---\nCARD: note\nre\u{301}sume\u{301}: yes\n---\n\nNo\u{308}el";
+    let nfc = "---\nQUILL: test_quill\ncafé: latte\n---\n\nCafé body.\n\n---\nCARD: note\nrésumé: yes\n---\n\nNöel";
+
+    // Off by default: the decomposed key is kept byte-for-byte.
+    let raw = decompose(nfd).unwrap();
+    assert!(raw.main().frontmatter().get("café").is_none());
+
+    let options = crate::document::ParseOptions {
+        normalize_unicode: true,
+        ..Default::default()
+    };
+    let from_nfd = Document::from_markdown_with_options(nfd, &options)
+        .unwrap()
+        .document;
+    let from_nfc = Document::from_markdown_with_options(nfc, &options)
+        .unwrap()
+        .document;
+
+    assert_eq!(
+        from_nfd.main().frontmatter().get("café").unwrap().as_str(),
+        Some("latte")
+    );
+    assert_eq!(
+        from_nfd.cards()[0].frontmatter().get("résumé"),
+        from_nfc.cards()[0].frontmatter().get("résumé")
+    );
+    assert_eq!(from_nfd.main().body(), from_nfc.main().body());
+    assert_eq!(from_nfd, from_nfc);
+}
+
+#[test]
+fn test_normalize_unicode_merges_colliding_keys() {
+    let markdown = "---\nQUILL: test_quill\n# note\ncafé: first\ncafe\u{301}: second\n---\n";
+    let options = crate::document::ParseOptions {
+        normalize_unicode: true,
+        ..Default::default()
+    };
+    let doc = Document::from_markdown_with_options(markdown, &options)
+        .unwrap()
+        .document;
+    assert_eq!(doc.main().frontmatter().len(), 1);
+    assert_eq!(
+        doc.main().frontmatter().get("café").unwrap().as_str(),
+        Some("second")
+    );
+    assert_eq!(doc.main().frontmatter().items().len(), 2);
+}
+
 #[test]
 fn test_unicode_in_yaml_values() {
     let markdown = "---\nQUILL: test_quill\ntitle: 你好世界 🎉\n---\n\nBody.";
@@ -2153,6 +2238,86 @@ Card body here.
     assert_eq!(cards[0]["BODY"], "\nCard body here.\n");
 }
 
+#[test]
+fn test_strip_frontmatter_global_only() {
+    let markdown = "---\nQUILL: q\ntitle: T\n---\n\n# Heading\n\nText with <<chevrons>>.\n";
+    assert_eq!(
+        crate::document::strip_frontmatter(markdown).unwrap(),
+        "# Heading\n\nText with <<chevrons>>."
+    );
+}
+
+#[test]
+fn test_strip_frontmatter_keeps_card_bodies_in_order() {
+    let markdown = "---\nQUILL: q\n---\n\nIntro.\n\n---\nCARD: a\nx: 1\n---\n\nFirst card.\n\n---\nCARD: b\n---\n\n---\nCARD: c\n---\n\n```\n---\nnot a fence\n```\n";
+    assert_eq!(
+        crate::document::strip_frontmatter(markdown).unwrap(),
+        "Intro.\n\nFirst card.\n\n```\n---\nnot a fence\n```"
+    );
+}
+
+#[test]
+fn test_strip_frontmatter_without_body_or_frontmatter() {
+    assert_eq!(
+        crate::document::strip_frontmatter("---\nQUILL: q\n---\n").unwrap(),
+        ""
+    );
+    // Without a QUILL fence there is nothing to strip: same error as parsing.
+    assert!(matches!(
+        crate::document::strip_frontmatter("Just text."),
+        Err(crate::error::ParseError::MissingQuillField(_))
+    ));
+}
+
+/// from_plate_json inverts to_plate_json for fields, cards, bodies, and QUILL.
+#[test]
+fn test_from_plate_json_round_trip() {
+    let markdown = r#"---
+QUILL: usaf_memo@1.2
+title: Test
+tags:
+  - a
+  - b
+meta:
+  count: 3
+  ratio: 0.5
+---
+
+Global body.
+
+---
+CARD: indorsement
+for: ORG
+---
+
+Card body here.
+
+---
+CARD: indorsement
+for: OTHER
+---
+"#;
+    let doc = Document::from_markdown(markdown).unwrap();
+    let wire = serde_json::to_string(&doc.to_plate_json()).unwrap();
+    let restored =
+        Document::from_plate_json(&serde_json::from_str(&wire).unwrap()).expect("from_plate_json");
+
+    assert_eq!(restored, doc);
+    assert_eq!(restored.quill_reference().to_string(), "usaf_memo@1.2");
+    assert_eq!(restored.cards()[1].body(), "");
+    assert_eq!(restored.to_plate_json(), doc.to_plate_json());
+}
+
+#[test]
+fn test_from_plate_json_rejects_bad_shapes() {
+    use serde_json::json;
+
+    assert!(Document::from_plate_json(&json!({"title": "x"})).is_err());
+    assert!(Document::from_plate_json(&json!({"QUILL": "q", "CARDS": {}})).is_err());
+    assert!(Document::from_plate_json(&json!({"QUILL": "q", "CARDS": [{"BODY": ""}]})).is_err());
+    assert!(Document::from_plate_json(&json!({"QUILL": "q", "Bad-Name": 1})).is_err());
+}
+
 /// to_plate_json parity: the QUILL key appears first.
 #[test]
 fn test_to_plate_json_quill_first() {
@@ -2210,3 +2375,207 @@ fn frontmatter_field_order_preserved_after_quill_removal() {
         "Frontmatter fields must preserve insertion order after QUILL removal"
     );
 }
+
+#[test]
+fn strict_mode_accepts_allowed_card() {
+    use crate::document::assemble::decompose_with_options;
+    use crate::document::ParseOptions;
+
+    let markdown = "---\nQUILL: q\n---\n\n---\nCARD: product\nname: Widget\n---\n\nBody\n";
+    let (doc, _) = decompose_with_options(markdown, &ParseOptions::strict(["product"])).unwrap();
+    assert_eq!(doc.cards().len(), 1);
+    assert_eq!(doc.cards()[0].tag(), "product");
+}
+
+#[test]
+fn strict_mode_rejects_unknown_card() {
+    use crate::document::assemble::decompose_with_options;
+    use crate::document::ParseOptions;
+    use crate::error::ParseError;
+
+    let markdown = "---\nQUILL: q\n---\n\nIntro\n\n---\nCARD: prodct\nname: Widget\n---\n";
+    let err = decompose_with_options(markdown, &ParseOptions::strict(["product"])).unwrap_err();
+    match &err {
+        ParseError::UnknownCard {
+            tag,
+            line,
+            block_index,
+            ..
+        } => {
+            assert_eq!(tag, "prodct");
+            assert_eq!(*line, 7);
+            assert_eq!(*block_index, 1);
+        }
+        other => panic!("expected UnknownCard, got {other:?}"),
+    }
+    let diag = err.to_diagnostic();
+    assert_eq!(diag.code.as_deref(), Some("parse::unknown_card"));
+    assert!(diag.hint.unwrap().contains("product"));
+}
+
+#[test]
+fn lenient_mode_ignores_allowed_cards() {
+    use crate::document::assemble::decompose_with_options;
+    use crate::document::ParseOptions;
+
+    let markdown = "---\nQUILL: q\n---\n\n---\nCARD: prodct\n---\n";
+    let options = ParseOptions {
+        strict: false,
+        allowed_cards: vec!["product".to_string()],
+        ..Default::default()
+    };
+    let (doc, _) = decompose_with_options(markdown, &options).unwrap();
+    assert_eq!(doc.cards()[0].tag(), "prodct");
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct MemoFields {
+    title: String,
+    copies: u32,
+    recipients: Vec<String>,
+    body: String,
+}
+
+#[test]
+fn test_deserialize_fields_into_struct() {
+    let doc = Document::from_markdown(
+        "---\nQUILL: memo\ntitle: Status\ncopies: 3\nrecipients:\n  - Ada\n  - Grace\n---\n\nAll well.",
+    )
+    .unwrap();
+
+    let fields: MemoFields = doc.deserialize_fields().unwrap();
+    assert_eq!(fields.title, "Status");
+    assert_eq!(fields.copies, 3);
+    assert_eq!(fields.recipients, vec!["Ada", "Grace"]);
+    assert_eq!(fields.body, "\nAll well.");
+}
+
+#[test]
+fn test_deserialize_fields_type_mismatch() {
+    let doc = Document::from_markdown(
+        "---\nQUILL: memo\ntitle: Status\ncopies: many\nrecipients: []\n---\n",
+    )
+    .unwrap();
+
+    let err = doc.deserialize_fields::<MemoFields>().unwrap_err();
+    assert!(err.is_data(), "expected a type error, got: {}", err);
+}
+
+#[test]
+fn test_diff_cards_finds_changed_card() {
+    let old = Document::from_markdown(
+        "---\nQUILL: catalog\n---\n\nIntro\n\n---\nCARD: item\nname: A\n---\n\nFirst\n\n---\nCARD: item\nname: B\n---\n\nSecond\n\n---\nCARD: item\nname: C\n---\n\nThird\n",
+    )
+    .unwrap();
+    let new = Document::from_markdown(
+        "---\nQUILL: catalog\n---\n\nIntro\n\n---\nCARD: item\nname: A\n---\n\nFirst\n\n---\nCARD: item\nname: B2\n---\n\nSecond\n\n---\nCARD: item\nname: C\n---\n\nThird\n",
+    )
+    .unwrap();
+
+    let diff = old.diff_cards(&new);
+    assert!(!diff.main_changed);
+    assert_eq!(diff.changed, vec![1]);
+    assert_eq!(diff.removed, 0);
+    assert!(old.diff_cards(&old.clone()).is_empty());
+
+    let truncated = Document::from_markdown(
+        "---\nQUILL: catalog\n---\n\nNew intro\n\n---\nCARD: item\nname: A\n---\n\nFirst\n",
+    )
+    .unwrap();
+    let diff = old.diff_cards(&truncated);
+    assert!(diff.main_changed);
+    assert!(diff.changed.is_empty());
+    assert_eq!(diff.removed, 2);
+}
+
+#[test]
+fn test_dump_context_json_and_yaml() {
+    use crate::document::DumpFormat;
+
+    let doc = Document::from_markdown(
+        "---\nQUILL: memo\nauthor:\n  name: Ada\n  tags: [a, b]\n---\n\nBody\n",
+    )
+    .unwrap();
+
+    let json = doc.dump_context(DumpFormat::Json);
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, doc.to_plate_json());
+    assert!(json.contains("\n  \"author\": {"), "not pretty: {}", json);
+
+    let yaml = doc.dump_context(DumpFormat::Yaml);
+    let parsed: serde_json::Value = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(parsed, doc.to_plate_json());
+    assert!(
+        yaml.contains("author:\n  name: Ada"),
+        "not block YAML: {}",
+        yaml
+    );
+}
+
+#[test]
+fn test_bom_prefixed_document_parses_like_bom_free() {
+    let markdown =
+        "---\nQUILL: memo\ntitle: T\n---\n\nIntro\n\n---\nCARD: note\n---\n\nCard body\n";
+    let plain = Document::from_markdown(markdown).unwrap();
+    let bom = Document::from_markdown(&format!("\u{FEFF}{}", markdown)).unwrap();
+
+    assert_eq!(bom, plain);
+    assert_eq!(bom.main().body(), "\nIntro\n");
+    assert_eq!(bom.cards()[0].body(), "\nCard body\n");
+}
+
+#[test]
+fn test_body_spans_map_to_source() {
+    let markdown = "---\nQUILL: catalog\n---\n\nIntro\n\n---\nCARD: item\nname: A\n---\n\nFirst «item»\n\n---\nCARD: item\nname: B\n---\n\nSecond item\n";
+    let output = Document::from_markdown_with_warnings(markdown).unwrap();
+    let spans = &output.body_spans;
+    assert_eq!(&markdown[spans.main.clone()], "\nIntro\n");
+    assert_eq!(spans.cards.len(), 2);
+    for (card, span) in output.document.cards().iter().zip(&spans.cards) {
+        assert_eq!(&markdown[span.clone()], card.body());
+    }
+    assert_eq!(&markdown[spans.cards[0].clone()], "\nFirst «item»\n");
+    assert_eq!(&markdown[spans.cards[1].clone()], "\nSecond item\n");
+
+    // A leading BOM shifts every span by its three bytes.
+    let with_bom = format!("\u{FEFF}{}", markdown);
+    let bom_spans = Document::from_markdown_with_warnings(&with_bom)
+        .unwrap()
+        .body_spans;
+    assert_eq!(
+        bom_spans.cards[1],
+        spans.cards[1].start + 3..spans.cards[1].end + 3
+    );
+    assert_eq!(&with_bom[bom_spans.cards[1].clone()], "\nSecond item\n");
+
+    let built = crate::document::ParseOutput::new(output.document.clone(), Vec::new());
+    assert_eq!(built.body_spans, crate::document::BodySpans::default());
+    assert_eq!(built.with_body_spans(spans.clone()).body_spans, *spans);
+}
+
+#[test]
+fn test_shadowed_field_names_warn() {
+    let markdown = "---\nQUILL: test_quill\nContent: x\ntitle: T\n---\n\nBody\n\n---\nCARD: note\nbody: y\n---\n";
+    let options = crate::document::ParseOptions {
+        shadowed_names: vec!["Content".to_string()],
+        ..Default::default()
+    };
+    let output = Document::from_markdown_with_options(markdown, &options).unwrap();
+    let shadowed: Vec<_> = output
+        .warnings
+        .iter()
+        .filter(|w| w.code.as_deref() == Some("parse::shadowed_name"))
+        .collect();
+    assert_eq!(shadowed.len(), 2, "{:?}", shadowed);
+    assert!(shadowed[0].message.contains("`Content` in frontmatter"));
+    assert!(shadowed[1].message.contains("`body` in card 'note'"));
+    assert!(shadowed[1].message.contains("reserved `BODY`"));
+    assert_eq!(output.document.warnings(), output.warnings.as_slice());
+
+    // Unchecked by default, and benign names never warn.
+    let output = Document::from_markdown_with_warnings(markdown).unwrap();
+    assert!(output.warnings.is_empty());
+    let benign = "---\nQUILL: test_quill\ntitle: T\n---\n\nBody\n";
+    let output = Document::from_markdown_with_options(benign, &options).unwrap();
+    assert!(output.warnings.is_empty());
+}
@@ -3,6 +3,7 @@
 use crate::document::edit::{is_reserved_name, is_valid_field_name, EditError, RESERVED_NAMES};
 use crate::document::sentinel::is_valid_tag_name;
 use crate::document::{Card, Document};
+use crate::error::MAX_INTERPOLATED_SIZE;
 use crate::value::QuillValue;
 use crate::version::QuillReference;
 use std::str::FromStr;
@@ -587,3 +588,171 @@ fn test_mutators_do_not_touch_warnings() {
 
     assert_eq!(doc.warnings(), initial_warnings.as_slice());
 }
+
+// ── Field references ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_references_single_and_chain() {
+    let mut doc = Document::from_markdown(
+        "---\nQUILL: test_quill\ntitle: Report\nyear: 2025\nfull_title: \"{{ title }} — {{ year }}\"\nheading: \"{{ full_title }} (draft)\"\ncount: 3\n---\n\nBody {{ title }}\n\n---\nCARD: note\nlabel: \"{{ heading }} / {{ name }}\"\nname: Note\n---\n",
+    )
+    .unwrap();
+    doc.resolve_references().unwrap();
+
+    let field = |card: &Card, key: &str| card.frontmatter().get(key).unwrap().clone();
+    assert_eq!(field(doc.main(), "full_title"), qv("Report — 2025"));
+    assert_eq!(field(doc.main(), "heading"), qv("Report — 2025 (draft)"));
+    assert_eq!(field(doc.main(), "count"), qv_int(3));
+    assert_eq!(doc.main().body(), "\nBody {{ title }}\n");
+    assert_eq!(
+        field(&doc.cards()[0], "label"),
+        qv("Report — 2025 (draft) / Note")
+    );
+}
+
+#[test]
+fn test_resolve_references_cycle_errors() {
+    let mut doc = Document::from_markdown(
+        "---\nQUILL: test_quill\na: \"{{ b }}\"\nb: \"x {{ c }}\"\nc: \"{{ a }}\"\nd: \"{{ a }}\"\n---\n",
+    )
+    .unwrap();
+    let before = doc.clone();
+
+    let err = doc.resolve_references().unwrap_err();
+    assert_eq!(
+        err,
+        EditError::ReferenceCycle(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "a".to_string()
+        ])
+    );
+    assert_eq!(
+        err.to_string(),
+        "field references form a cycle: a -> b -> c -> a"
+    );
+    assert_eq!(doc, before);
+}
+
+#[test]
+fn test_resolve_references_depth_limit() {
+    use crate::error::MAX_EXPANSION_DEPTH;
+    // f0 -> f1 -> ... -> fN, no cycle.
+    let chain = |depth: usize| {
+        let mut markdown = String::from("---\nQUILL: test_quill\n");
+        for i in 0..depth {
+            markdown.push_str(&format!("f{}: \"{{{{ f{} }}}}\"\n", i, i + 1));
+        }
+        markdown.push_str(&format!("f{}: end\n---\n", depth));
+        Document::from_markdown(&markdown).unwrap()
+    };
+
+    let mut doc = chain(MAX_EXPANSION_DEPTH - 1);
+    doc.resolve_references().unwrap();
+    assert_eq!(doc.main().frontmatter().get("f0"), Some(&qv("end")));
+
+    let mut doc = chain(MAX_EXPANSION_DEPTH);
+    let before = doc.clone();
+    let err = doc.resolve_references().unwrap_err();
+    let EditError::ReferencesTooDeep { chain, max } = &err else {
+        panic!("expected ReferencesTooDeep, got {:?}", err);
+    };
+    assert_eq!(*max, MAX_EXPANSION_DEPTH);
+    assert_eq!(chain.len(), MAX_EXPANSION_DEPTH + 1);
+    assert_eq!(chain[0], "f0");
+    assert!(err
+        .to_string()
+        .starts_with("field references are nested deeper than 32 levels: f0 -> f1"));
+    assert_eq!(doc, before);
+}
+
+#[test]
+fn test_interpolate_fields_with_limit() {
+    use crate::document::interpolate_fields_with_limit;
+    let mut fields = indexmap::IndexMap::new();
+    fields.insert("name".to_string(), qv("Ada"));
+
+    let out = interpolate_fields_with_limit("Dear {{ name }}, {{ name }}!", &fields, 32).unwrap();
+    assert_eq!(out, "Dear Ada, Ada!");
+
+    let err =
+        interpolate_fields_with_limit("{{ name }}{{ name }}{{ name }}", &fields, 8).unwrap_err();
+    assert_eq!(err, EditError::InterpolationTooLarge { max: 8 });
+    assert_eq!(
+        err.to_string(),
+        "field interpolation output exceeds the 8-byte limit"
+    );
+}
+
+#[test]
+fn test_resolve_references_exponential_chain_is_capped() {
+    // Each field doubles the previous one: 2^30 copies without the cap.
+    let mut markdown = String::from("---\nQUILL: test_quill\nf0: \"0123456789\"\n");
+    for i in 1..=30 {
+        markdown.push_str(&format!(
+            "f{}: \"{{{{ f{} }}}}{{{{ f{} }}}}\"\n",
+            i,
+            i - 1,
+            i - 1
+        ));
+    }
+    markdown.push_str("---\n");
+    let mut doc = Document::from_markdown(&markdown).unwrap();
+    let before = doc.clone();
+
+    let err = doc.resolve_references().unwrap_err();
+    assert!(matches!(err, EditError::InterpolationTooLarge { .. }));
+    assert_eq!(doc, before);
+}
+
+#[test]
+fn test_resolve_references_fan_out_shares_one_budget() {
+    // f18 is 2.5 MB, under the per-field cap; copying it into many fields
+    // and cards must still exhaust the document-wide budget.
+    let mut markdown = String::from(
+        "---
+QUILL: test_quill
+f0: \"0123456789\"\n",
+    );
+    for i in 1..=18 {
+        markdown.push_str(&format!(
+            "f{}: \"{{{{ f{} }}}}{{{{ f{} }}}}\"\n",
+            i,
+            i - 1,
+            i - 1
+        ));
+    }
+    markdown.push_str("---\n");
+    let within_budget = Document::from_markdown(&markdown).unwrap();
+    for i in 0..4 {
+        markdown.push_str(&format!(
+            "\n---\nCARD: copy\nx{}: \"{{{{ f18 }}}}\"\n---\n",
+            i
+        ));
+    }
+    let mut doc = Document::from_markdown(&markdown).unwrap();
+    let before = doc.clone();
+
+    let err = doc.resolve_references().unwrap_err();
+    assert!(matches!(
+        err,
+        EditError::InterpolationTooLarge {
+            max: MAX_INTERPOLATED_SIZE
+        }
+    ));
+    assert_eq!(doc, before);
+
+    let mut doc = within_budget;
+    doc.resolve_references().unwrap();
+    assert_eq!(
+        doc.main()
+            .frontmatter()
+            .get("f18")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .len(),
+        10 << 18
+    );
+}
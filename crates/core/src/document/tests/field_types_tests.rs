@@ -0,0 +1,33 @@
+use crate::document::Document;
+use crate::value::FieldType;
+
+#[test]
+fn test_field_types_classification() {
+    let markdown = "---\nQUILL: q\ntitle: Report\ncount: 3\nitems:\n  - name: a\n  - name: b\nreviewer: null\n---\n\nBody\n";
+    let doc = Document::from_markdown(markdown).unwrap();
+    let types = doc.field_types();
+
+    assert_eq!(types["title"], FieldType::String);
+    assert_eq!(types["count"], FieldType::Number);
+    assert_eq!(
+        types["items"],
+        FieldType::Array(Some(Box::new(FieldType::Object)))
+    );
+    assert_eq!(types["reviewer"], FieldType::Null);
+    assert_eq!(types["BODY"], FieldType::String);
+    assert_eq!(types.len(), 5);
+}
+
+#[test]
+fn test_field_types_mixed_and_empty_arrays() {
+    let markdown = "---\nQUILL: q\nmixed: [1, two]\nempty: []\nflags: [true, false]\n---\n";
+    let doc = Document::from_markdown(markdown).unwrap();
+    let types = doc.field_types();
+
+    assert_eq!(types["mixed"], FieldType::Array(None));
+    assert_eq!(types["empty"], FieldType::Array(None));
+    assert_eq!(
+        types["flags"],
+        FieldType::Array(Some(Box::new(FieldType::Bool)))
+    );
+}
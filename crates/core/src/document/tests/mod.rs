@@ -4,5 +4,7 @@ mod edit_tests;
 mod emit_idempotence_tests;
 mod emit_stability_tests;
 mod emit_tests;
+mod field_types_tests;
 mod lossiness_tests;
 mod number_edge_tests;
+mod stream_tests;
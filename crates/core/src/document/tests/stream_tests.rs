@@ -0,0 +1,114 @@
+use std::io::Cursor;
+
+use crate::document::assemble::decompose;
+use crate::document::stream::decompose_blocks;
+use crate::document::Card;
+use crate::error::ParseError;
+
+fn stream_all(markdown: &str) -> Result<Vec<Card>, ParseError> {
+    decompose_blocks(Cursor::new(markdown.as_bytes())).collect()
+}
+
+#[test]
+fn test_stream_yields_main_then_cards() {
+    let markdown = "---\nQUILL: log_quill\ntitle: Nightly\n---\n\nIntro.\n\n\
+                    ---\nCARD: entry\nlevel: info\n---\n\nFirst.\n\n\
+                    ---\nCARD: entry\nlevel: warn\n---\n\nSecond.\n\n\
+                    ---\nCARD: entry\nlevel: error\n---\n\nThird.\n";
+
+    let mut stream = decompose_blocks(Cursor::new(markdown.as_bytes()));
+
+    let main = stream.next().unwrap().unwrap();
+    assert!(main.is_main());
+    assert_eq!(main.tag(), "log_quill");
+    assert_eq!(
+        main.frontmatter().get("title").and_then(|v| v.as_str()),
+        Some("Nightly")
+    );
+    assert_eq!(main.body(), "\nIntro.\n");
+
+    let levels: Vec<String> = stream
+        .by_ref()
+        .map(|card| {
+            let card = card.unwrap();
+            assert_eq!(card.tag(), "entry");
+            card.frontmatter()
+                .get("level")
+                .and_then(|v| v.as_str())
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(levels, vec!["info", "warn", "error"]);
+    assert!(stream.next().is_none());
+    assert!(stream.warnings().is_empty());
+}
+
+#[test]
+fn test_stream_matches_eager_decompose() {
+    let markdown = concat!(
+        "---\nQUILL: q\n# banner\na: 1\n---\n\nBody\n\n",
+        "```\n---\nCARD: hidden\n---\n```\n\n",
+        "---\nNOTCARD: x\n---\n\n",
+        "---\nCARD: note\nb: [1, 2]\n---\n\nNote body\n",
+    );
+
+    let doc = decompose(markdown).unwrap();
+    let streamed = stream_all(markdown).unwrap();
+
+    assert_eq!(streamed.len(), 1 + doc.cards().len());
+    assert_eq!(&streamed[0], doc.main());
+    assert_eq!(&streamed[1..], doc.cards());
+}
+
+#[test]
+fn test_stream_near_miss_warning_collected() {
+    let markdown = "---\nQUILL: q\n---\n\nBody\n\n---\nCARDS_X: x\n---\n";
+    let mut stream = decompose_blocks(Cursor::new(markdown.as_bytes()));
+    assert_eq!(stream.by_ref().count(), 1);
+    assert_eq!(stream.warnings().len(), 1);
+    assert_eq!(
+        stream.warnings()[0].code.as_deref(),
+        Some("parse::near_miss_sentinel")
+    );
+}
+
+#[test]
+fn test_stream_errors() {
+    assert!(matches!(stream_all(""), Err(ParseError::EmptyInput(_))));
+    assert!(matches!(
+        stream_all("# Just prose\n"),
+        Err(ParseError::MissingQuillField(_))
+    ));
+
+    let unclosed = "---\nQUILL: q\n---\n\nBody\n\n---\nCARD: item\nvalue: 1\n";
+    assert!(matches!(
+        stream_all(unclosed),
+        Err(ParseError::InvalidStructure(_))
+    ));
+}
+
+#[test]
+fn test_stream_caps_unterminated_line() {
+    // An endless stream without a newline must fail instead of buffering.
+    let reader = std::io::BufReader::new(std::io::repeat(b'a'));
+    let first = decompose_blocks(reader).next().unwrap();
+    assert!(matches!(
+        first,
+        Err(ParseError::InputTooLarge { size, max }) if size == max + 1
+    ));
+}
+
+#[test]
+fn test_stream_yaml_error_line_is_absolute() {
+    let markdown = "---\nQUILL: q\n---\n\nBody\n\n---\nCARD: item\nbad: {\n---\n";
+    let eager = decompose(markdown).unwrap_err();
+    let streamed = stream_all(markdown).unwrap_err();
+    match (eager, streamed) {
+        (
+            ParseError::YamlErrorWithLocation { line: a, .. },
+            ParseError::YamlErrorWithLocation { line: b, .. },
+        ) => assert_eq!(a, b),
+        other => panic!("expected YAML errors, got {:?}", other),
+    }
+}
@@ -10,6 +10,7 @@
 //! ## Key Types
 //!
 //! - [`RenderError`]: Main error enum for rendering operations
+//! - [`QuillError`]: Errors loading a quill from its file tree
 
 //! - [`Diagnostic`]: Structured diagnostic information
 //! - [`Location`]: Source file location (file, line, column)
@@ -87,6 +88,21 @@ pub const MAX_INPUT_SIZE: usize = 10 * 1024 * 1024;
 /// Maximum YAML size (1 MB)
 pub const MAX_YAML_SIZE: usize = 1024 * 1024;
 
+/// Maximum size of text produced by `{{ name }}` field interpolation (10 MB)
+/// Prevents body templates and chained field references from expanding a
+/// small document into huge output
+pub const MAX_INTERPOLATED_SIZE: usize = 10 * 1024 * 1024;
+
+/// Maximum depth of chained `{{ name }}` references and quill `extends` (32 levels)
+///
+/// Keeps long non-cyclic chains from recursing without bound.
+pub const MAX_EXPANSION_DEPTH: usize = 32;
+
+/// Maximum YAML events replayed through aliases per metadata block (10,000)
+///
+/// Re-exported from [`crate::document::limits::MAX_YAML_ALIAS_EXPANSION`].
+pub use crate::document::limits::MAX_YAML_ALIAS_EXPANSION;
+
 /// Maximum nesting depth for markdown structures (100 levels)
 pub const MAX_NESTING_DEPTH: usize = 100;
 
@@ -128,6 +144,26 @@ pub struct Location {
     pub column: u32,
 }
 
+/// Location of `byte_offset` within `source`, in `file`.
+///
+/// Lines are counted by `\n`; the column counts characters, not bytes, so
+/// text such as emoji before the offset does not shift it. An offset past
+/// the end or inside a multi-byte character is moved back to the preceding
+/// character boundary.
+pub fn offset_to_location(file: impl Into<String>, source: &str, byte_offset: usize) -> Location {
+    let mut offset = byte_offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+    Location {
+        file: file.into(),
+        line: before.matches('\n').count() as u32 + 1,
+        column: before[line_start..].chars().count() as u32 + 1,
+    }
+}
+
 /// Structured diagnostic information.
 ///
 /// `source_chain` is a flat list of error messages from any attached
@@ -282,6 +318,21 @@ pub enum ParseError {
         block_index: usize,
     },
 
+    /// Strict parse mode rejected a `CARD:` tag outside the allowed set.
+    ///
+    /// Emitted as code `parse::unknown_card`.
+    #[error("Unknown card `{tag}` at line {line} (allowed: {allowed:?})")]
+    UnknownCard {
+        /// The offending `CARD:` tag
+        tag: String,
+        /// Line of the card's opening fence (1-indexed)
+        line: usize,
+        /// Index of the metadata block (0-indexed)
+        block_index: usize,
+        /// Card tags the parse was restricted to
+        allowed: Vec<String>,
+    },
+
     /// Other parsing errors
     #[error("{0}")]
     Other(String),
@@ -314,6 +365,24 @@ impl ParseError {
                 ),
             )
             .with_code("parse::yaml_error_with_location".to_string()),
+            ParseError::UnknownCard {
+                tag,
+                line,
+                block_index,
+                allowed,
+            } => Diagnostic::new(
+                Severity::Error,
+                format!(
+                    "Unknown card `{}` at line {} (block {})",
+                    tag, line, block_index
+                ),
+            )
+            .with_code("parse::unknown_card".to_string())
+            .with_hint(if allowed.is_empty() {
+                "This quill declares no card types; remove the card block".to_string()
+            } else {
+                format!("Allowed cards: {}", allowed.join(", "))
+            }),
             ParseError::Other(msg) => Diagnostic::new(Severity::Error, msg.clone()),
         }
     }
@@ -337,6 +406,170 @@ impl From<&str> for ParseError {
     }
 }
 
+/// Error type for loading a quill from its file tree.
+#[derive(thiserror::Error, Debug)]
+pub enum QuillError {
+    /// `Quill.yaml` is absent from the file tree.
+    #[error("Quill.yaml not found in file tree")]
+    MissingConfig,
+
+    /// `Quill.yaml` is not valid UTF-8 or could not be parsed.
+    #[error("Invalid Quill.yaml: {detail}")]
+    InvalidConfig {
+        /// Parser or decoder message
+        detail: String,
+    },
+
+    /// The plate file named by `plate_file` is absent from the file tree.
+    #[error("Plate file '{file}' not found in file tree")]
+    MissingPlate {
+        /// Plate path as declared in `Quill.yaml`
+        file: String,
+    },
+
+    /// The plate file is not valid UTF-8.
+    #[error("Plate file '{file}' is not valid UTF-8")]
+    PlateNotUtf8 {
+        /// Plate path as declared in `Quill.yaml`
+        file: String,
+    },
+
+    /// The example markdown file is missing, unreadable, or outside the quill.
+    #[error("{detail}")]
+    InvalidExample {
+        /// Example path
+        file: String,
+        /// What went wrong
+        detail: String,
+    },
+
+    /// The body template file is missing, not UTF-8, or outside the quill.
+    #[error("{detail}")]
+    InvalidBodyTemplate {
+        /// Body template path as declared in `Quill.yaml`
+        file: String,
+        /// What went wrong
+        detail: String,
+    },
+
+    /// An asset listed in `checksums:` or `assets:` is absent from the file
+    /// tree.
+    #[error("Asset '{file}' listed in Quill.yaml not found in file tree")]
+    MissingAsset {
+        /// Asset path as declared in `Quill.yaml`
+        file: String,
+    },
+
+    /// An asset's SHA-256 digest differs from the one pinned in `checksums:`.
+    #[error("Checksum mismatch for '{file}': expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// Asset path as declared in `Quill.yaml`
+        file: String,
+        /// Digest pinned in `Quill.yaml`
+        expected: String,
+        /// Digest of the bundled file
+        actual: String,
+    },
+
+    /// An asset name is absolute or climbs out of the asset directory.
+    #[error("Asset path '{path}' escapes the quill")]
+    InvalidAssetPath {
+        /// Asset name as requested
+        path: String,
+    },
+
+    /// `quill.extends` names a base quill that was not merged into the tree.
+    #[error("Quill extends '{base}', which is only resolved when loading from a path")]
+    UnresolvedExtends {
+        /// Base quill as declared in `Quill.yaml`
+        base: String,
+    },
+
+    /// `quill.extends` entries lead back to a quill already in the chain.
+    #[error("Quill inheritance forms a cycle: {}", .chain.join(" -> "))]
+    ExtendsCycle {
+        /// Quill directories in inheritance order, ending with the repeated one
+        chain: Vec<String>,
+    },
+
+    /// `quill.extends` chains more than `max` base quills.
+    #[error("Quill inheritance is deeper than {max} levels: {}", .chain.join(" -> "))]
+    ExtendsTooDeep {
+        /// Quill directories in inheritance order, up to where the limit hit
+        chain: Vec<String>,
+        /// Maximum allowed depth
+        max: usize,
+    },
+}
+
+impl QuillError {
+    /// A short, actionable hint for fixing the error.
+    pub fn hint(&self) -> String {
+        match self {
+            QuillError::MissingConfig => {
+                "Add a Quill.yaml with a `quill:` section at the quill root".to_string()
+            }
+            QuillError::InvalidConfig { .. } => {
+                "Check Quill.yaml for YAML syntax errors and required `quill:` fields".to_string()
+            }
+            QuillError::MissingPlate { file } => format!(
+                "Add '{}' to the quill or fix `plate_file` in Quill.yaml",
+                file
+            ),
+            QuillError::PlateNotUtf8 { file } => format!("Re-save '{}' as UTF-8", file),
+            QuillError::InvalidExample { file, .. } => format!(
+                "Check that '{}' exists inside the quill and is UTF-8 encoded",
+                file
+            ),
+            QuillError::InvalidBodyTemplate { file, .. } => format!(
+                "Add '{}' to the quill or fix `body_template_file` in Quill.yaml",
+                file
+            ),
+            QuillError::MissingAsset { file } => format!(
+                "Add '{}' to the quill or remove it from `checksums`/`assets` in Quill.yaml",
+                file
+            ),
+            QuillError::ChecksumMismatch { file, .. } => format!(
+                "'{}' differs from the pinned asset; restore the original file or update its checksum",
+                file
+            ),
+            QuillError::InvalidAssetPath { .. } => {
+                "Use a relative asset name without `..` components".to_string()
+            }
+            QuillError::UnresolvedExtends { .. } => {
+                "Load the quill with `Quillmark::quill_from_path`, or layer it over its base with `quill::inherit`".to_string()
+            }
+            QuillError::ExtendsCycle { .. } => {
+                "Remove the `extends` entry that points back into the chain".to_string()
+            }
+            QuillError::ExtendsTooDeep { .. } => {
+                "Flatten the inheritance chain by merging intermediate base quills".to_string()
+            }
+        }
+    }
+
+    /// Convert the quill error into a structured diagnostic
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let code = match self {
+            QuillError::MissingConfig => "quill::missing_config",
+            QuillError::InvalidConfig { .. } => "quill::invalid_config",
+            QuillError::MissingPlate { .. } => "quill::missing_plate",
+            QuillError::PlateNotUtf8 { .. } => "quill::plate_not_utf8",
+            QuillError::InvalidExample { .. } => "quill::invalid_example",
+            QuillError::InvalidBodyTemplate { .. } => "quill::invalid_body_template",
+            QuillError::MissingAsset { .. } => "quill::missing_asset",
+            QuillError::ChecksumMismatch { .. } => "quill::checksum_mismatch",
+            QuillError::InvalidAssetPath { .. } => "quill::invalid_asset_path",
+            QuillError::UnresolvedExtends { .. } => "quill::unresolved_extends",
+            QuillError::ExtendsCycle { .. } => "quill::extends_cycle",
+            QuillError::ExtendsTooDeep { .. } => "quill::extends_too_deep",
+        };
+        Diagnostic::new(Severity::Error, self.to_string())
+            .with_code(code.to_string())
+            .with_hint(self.hint())
+    }
+}
+
 /// Main error type for rendering operations.
 #[derive(thiserror::Error, Debug)]
 pub enum RenderError {
@@ -388,6 +621,20 @@ pub enum RenderError {
         /// Diagnostic information
         diag: Box<Diagnostic>,
     },
+
+    /// Document exceeds [`crate::RenderOptions::max_pages`]
+    #[error("{diag}")]
+    TooManyPages {
+        /// Diagnostic information
+        diag: Box<Diagnostic>,
+    },
+
+    /// Writing rendered output to the caller's sink failed (I/O error)
+    #[error("{diag}")]
+    OutputFailed {
+        /// Diagnostic information
+        diag: Box<Diagnostic>,
+    },
 }
 
 impl RenderError {
@@ -400,7 +647,9 @@ impl RenderError {
             | RenderError::FormatNotSupported { diag }
             | RenderError::UnsupportedBackend { diag }
             | RenderError::ValidationFailed { diag }
-            | RenderError::QuillConfig { diag } => vec![diag.as_ref()],
+            | RenderError::QuillConfig { diag }
+            | RenderError::TooManyPages { diag }
+            | RenderError::OutputFailed { diag } => vec![diag.as_ref()],
         }
     }
 }
@@ -418,7 +667,7 @@ impl From<ParseError> for RenderError {
 }
 
 /// Result type containing artifacts and warnings
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RenderResult {
     /// Generated output artifacts
     pub artifacts: Vec<crate::Artifact>,
@@ -443,6 +692,22 @@ impl RenderResult {
         self.warnings.push(warning);
         self
     }
+
+    /// The main artifact: the whole document for PDF and TXT, the first
+    /// rendered page for SVG and PNG. `None` when nothing was produced.
+    pub fn primary(&self) -> Option<&crate::Artifact> {
+        self.artifacts.first()
+    }
+
+    /// Whether the result holds one artifact per page for more than one page.
+    pub fn is_multi_page(&self) -> bool {
+        self.artifacts.iter().filter(|a| a.page.is_some()).count() > 1
+    }
+
+    /// Combined size of all artifacts in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.artifacts.iter().map(|a| a.bytes.len()).sum()
+    }
 }
 
 /// Helper to print structured errors
@@ -539,4 +804,57 @@ mod tests {
         assert_eq!(result.warnings.len(), 1);
         assert_eq!(result.warnings[0].message, "Test warning");
     }
+
+    #[test]
+    fn test_render_result_helpers_single_artifact() {
+        let pdf = crate::Artifact {
+            bytes: vec![0; 10],
+            output_format: OutputFormat::Pdf,
+            page: None,
+        };
+        let result = RenderResult::new(vec![pdf], OutputFormat::Pdf);
+
+        assert_eq!(result.primary().map(|a| a.bytes.len()), Some(10));
+        assert!(!result.is_multi_page());
+        assert_eq!(result.total_bytes(), 10);
+
+        let empty = RenderResult::new(vec![], OutputFormat::Pdf);
+        assert!(empty.primary().is_none());
+        assert_eq!(empty.total_bytes(), 0);
+    }
+
+    #[test]
+    fn test_render_result_helpers_multi_page() {
+        let page = |idx: usize, len: usize| crate::Artifact {
+            bytes: vec![0; len],
+            output_format: OutputFormat::Svg,
+            page: Some(idx),
+        };
+        let result = RenderResult::new(vec![page(0, 3), page(1, 4)], OutputFormat::Svg);
+
+        assert_eq!(result.primary().and_then(|a| a.page), Some(0));
+        assert!(result.is_multi_page());
+        assert_eq!(result.total_bytes(), 7);
+
+        let single = RenderResult::new(vec![page(2, 5)], OutputFormat::Svg);
+        assert!(!single.is_multi_page());
+    }
+
+    #[test]
+    fn test_offset_to_location_counts_characters() {
+        let source = "first\nab😀c error";
+        let offset = source.find("error").unwrap();
+        let loc = offset_to_location("input.md", source, offset);
+        assert_eq!((loc.line, loc.column), (2, 6));
+        assert_eq!(loc.file, "input.md");
+
+        // Inside the emoji: clamped back to its start.
+        let loc = offset_to_location("input.md", source, source.find('😀').unwrap() + 2);
+        assert_eq!((loc.line, loc.column), (2, 3));
+
+        let loc = offset_to_location("input.md", source, 0);
+        assert_eq!((loc.line, loc.column), (1, 1));
+        let loc = offset_to_location("input.md", source, source.len() + 10);
+        assert_eq!((loc.line, loc.column), (2, 11));
+    }
 }
@@ -0,0 +1,99 @@
+//! Markdown to HTML for previews.
+//!
+//! A backend-independent rendering of card bodies, meant for quick previews
+//! in an editor before a full render. Raw HTML in the Markdown is dropped
+//! and links with script-capable schemes are emptied, so the output is safe
+//! to insert into a page.
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// URL schemes that can execute code when a link or image is followed.
+const UNSAFE_SCHEMES: [&str; 3] = ["javascript:", "vbscript:", "data:"];
+
+/// Convert Markdown to sanitized HTML.
+///
+/// Supports the same CommonMark extensions as the Typst backend
+/// (strikethrough, tables). Raw HTML blocks and inline tags are removed.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+
+    let events = Parser::new_ext(markdown, options).filter_map(|event| match event {
+        Event::Html(_) | Event::InlineHtml(_) => None,
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Some(Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        })),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Some(Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        })),
+        other => Some(other),
+    });
+
+    let mut out = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut out, events);
+    out
+}
+
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .take(11)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if UNSAFE_SCHEMES.iter().any(|s| scheme.starts_with(s)) {
+        CowStr::Borrowed("")
+    } else {
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown_to_html;
+
+    #[test]
+    fn test_markdown_to_html_formatting() {
+        let html = markdown_to_html("Some **bold** text.\n\n- one\n- [two](https://example.com)\n");
+        assert_eq!(
+            html,
+            "<p>Some <strong>bold</strong> text.</p>\n<ul>\n<li>one</li>\n<li><a href=\"https://example.com\">two</a></li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_keeps_guillemets() {
+        assert_eq!(
+            markdown_to_html("«Bonjour» she said"),
+            "<p>«Bonjour» she said</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_strips_scripts() {
+        let html = markdown_to_html(
+            "<script>alert(1)</script>\n\nHi <b onclick=\"x()\">there</b> [link](JavaScript:alert(1))\n",
+        );
+        assert!(!html.contains("<script"), "{}", html);
+        assert!(!html.contains("onclick"), "{}", html);
+        assert!(!html.to_lowercase().contains("javascript:"), "{}", html);
+        assert!(html.contains("Hi there <a href=\"\">link</a>"), "{}", html);
+    }
+}
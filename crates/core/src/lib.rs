@@ -37,6 +37,7 @@
 //! - [`error`]: Structured error handling and diagnostics
 //! - [`types`]: Core rendering types (OutputFormat, Artifact, RenderOptions)
 //! - [`quill`]: QuillSource bundle and related types
+//! - [`html`]: Sanitized Markdown-to-HTML for previews
 //!
 //! ## Further Reading
 //!
@@ -45,17 +46,23 @@
 
 pub mod document;
 pub use document::{
-    Card, Document, EditError, Frontmatter, FrontmatterItem, ParseOutput, Sentinel,
+    decompose_blocks, strip_frontmatter, BodySpans, Card, CardDiff, CardStream, Document,
+    DumpFormat, EditError, Frontmatter, FrontmatterItem, ParseOptions, ParseOutput, Sentinel,
 };
 
 pub mod backend;
 pub use backend::Backend;
 
 pub mod error;
-pub use error::{Diagnostic, Location, ParseError, RenderError, RenderResult, Severity};
+pub use error::{
+    Diagnostic, Location, ParseError, QuillError, RenderError, RenderResult, Severity,
+};
+
+pub mod html;
+pub use html::markdown_to_html;
 
 pub mod types;
-pub use types::{Artifact, OutputFormat, RenderOptions};
+pub use types::{Artifact, OutputFormat, PdfStandard, RenderOptions, RenderOptionsBuilder};
 
 pub mod session;
 pub use session::RenderSession;
@@ -64,7 +71,7 @@ pub mod quill;
 pub use quill::{FileTreeNode, QuillIgnore, QuillSource};
 
 pub mod value;
-pub use value::QuillValue;
+pub use value::{FieldType, QuillValue};
 
 pub mod normalize;
 pub use normalize::{
@@ -72,5 +79,8 @@ pub use normalize::{
     NormalizationError,
 };
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 pub mod version;
 pub use version::{QuillReference, Version, VersionSelector};
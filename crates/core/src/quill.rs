@@ -1,8 +1,10 @@
 //! Quill source bundle types and implementations.
 
+mod checksum;
 mod config;
 mod formats;
 mod ignore;
+mod inherit;
 mod load;
 mod query;
 mod schema;
@@ -13,6 +15,8 @@ pub(crate) mod validation;
 
 pub use config::{CoercionError, QuillConfig};
 pub use ignore::QuillIgnore;
+pub use inherit::{extends_target, inherit};
+pub use query::FileEntry;
 pub use schema::build_transform_schema;
 pub use tree::FileTreeNode;
 pub use types::{
@@ -20,8 +24,12 @@ pub use types::{
 };
 
 use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
 
+use crate::error::QuillError;
 use crate::value::QuillValue;
+use crate::OutputFormat;
 
 /// A quill source bundle — pure data parsed from an authored quill directory.
 ///
@@ -34,9 +42,12 @@ pub struct QuillSource {
     pub(crate) name: String,
     pub(crate) backend_id: String,
     pub(crate) plate: Option<String>,
+    pub(crate) fragment_plate: Option<String>,
+    pub(crate) body_template: Option<String>,
     pub(crate) example: Option<String>,
     pub(crate) config: QuillConfig,
     pub(crate) files: FileTreeNode,
+    pub(crate) fingerprint: OnceLock<String>,
 }
 
 impl QuillSource {
@@ -45,11 +56,20 @@ impl QuillSource {
         &self.name
     }
 
-    /// The backend identifier declared in Quill.yaml (e.g. `"typst"`).
+    /// The backend identifier (e.g. `"typst"`): declared in Quill.yaml, or
+    /// inferred from the plate file extension.
     pub fn backend_id(&self) -> &str {
         &self.backend_id
     }
 
+    /// The backend identifier as declared in Quill.yaml, or `None` when it
+    /// was inferred from the plate file extension.
+    pub fn declared_backend(&self) -> Option<&str> {
+        self.config
+            .backend_declared
+            .then_some(self.backend_id.as_str())
+    }
+
     /// Quill-specific metadata parsed from Quill.yaml.
     pub fn metadata(&self) -> &HashMap<String, QuillValue> {
         &self.metadata
@@ -60,6 +80,18 @@ impl QuillSource {
         self.plate.as_deref()
     }
 
+    /// The fragment plate content (`quill.fragment_plate_file`), if the
+    /// quill declares one.
+    pub fn fragment_plate(&self) -> Option<&str> {
+        self.fragment_plate.as_deref()
+    }
+
+    /// The body template Markdown (`quill.body_template_file`), if the quill
+    /// declares one.
+    pub fn body_template(&self) -> Option<&str> {
+        self.body_template.as_deref()
+    }
+
     /// The example Markdown content, if the quill ships one.
     pub fn example(&self) -> Option<&str> {
         self.example.as_deref()
@@ -74,6 +106,55 @@ impl QuillSource {
     pub fn files(&self) -> &FileTreeNode {
         &self.files
     }
+
+    /// SHA-256 content fingerprint of the quill's whole file tree (lowercase
+    /// hex). Changes whenever any file — plate, asset, font — is added,
+    /// removed, or edited. Computed on first use.
+    pub fn fingerprint(&self) -> &str {
+        self.fingerprint
+            .get_or_init(|| checksum::tree_fingerprint(&self.files))
+    }
+
+    /// Quill-relative directory holding bundled assets (`quill.asset_dir`,
+    /// default `assets`).
+    pub fn asset_dir(&self) -> &str {
+        &self.config.asset_dir
+    }
+
+    /// Whether the asset at quill-relative `path` is used when rendering to
+    /// `format`. Assets the `assets:` section of Quill.yaml does not list
+    /// apply to every format; a `None` format keeps every asset.
+    pub fn asset_applies_to(&self, path: &str, format: Option<OutputFormat>) -> bool {
+        match (self.config.asset_formats.get(path), format) {
+            (Some(formats), Some(format)) => formats.contains(&format),
+            _ => true,
+        }
+    }
+
+    /// Resolve an asset name against [`QuillSource::asset_dir`].
+    ///
+    /// Absolute names and `..` components are rejected so an asset can never
+    /// escape the quill. The file need not exist.
+    pub fn asset_path(&self, name: &str) -> Result<PathBuf, QuillError> {
+        if !is_contained_path(name) {
+            return Err(QuillError::InvalidAssetPath {
+                path: name.to_string(),
+            });
+        }
+        Ok(Path::new(self.asset_dir()).join(name))
+    }
+}
+
+/// Whether `path` names something strictly inside the directory it is joined
+/// to: relative, with no `..` or root components and at least one name.
+pub fn is_contained_path(path: &str) -> bool {
+    let mut components = Path::new(path).components();
+    !path.starts_with('/')
+        && !path.starts_with('\\')
+        && components
+            .clone()
+            .any(|c| matches!(c, Component::Normal(_)))
+        && components.all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
 }
 
 impl std::fmt::Debug for QuillSource {
@@ -85,6 +166,8 @@ impl std::fmt::Debug for QuillSource {
                 "plate",
                 &self.plate.as_ref().map(|s| format!("<{} bytes>", s.len())),
             )
+            .field("fragment_plate", &self.fragment_plate.is_some())
+            .field("body_template", &self.body_template.is_some())
             .field("example", &self.example.is_some())
             .field("files", &"<FileTreeNode>")
             .finish()
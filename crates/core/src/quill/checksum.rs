@@ -0,0 +1,88 @@
+//! SHA-256 digests for the `checksums:` section of Quill.yaml.
+//!
+//! Quill authors pin the expected digest of bundled assets (fonts, images)
+//! so a tampered bundle fails to load.
+
+use sha2::{Digest, Sha256};
+
+use crate::error::QuillError;
+
+use super::FileTreeNode;
+
+/// Lowercase hex SHA-256 digest of `data`.
+pub(super) fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Check every pinned asset in `checksums` against the file tree. Assets not
+/// listed are not checked.
+pub(super) fn verify_checksums<'a>(
+    checksums: impl IntoIterator<Item = (&'a String, &'a String)>,
+    root: &FileTreeNode,
+) -> Result<(), QuillError> {
+    for (file, expected) in checksums {
+        let contents = root
+            .get_file(file)
+            .ok_or_else(|| QuillError::MissingAsset { file: file.clone() })?;
+        let actual = sha256_hex(contents);
+        if actual != *expected {
+            return Err(QuillError::ChecksumMismatch {
+                file: file.clone(),
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Content digest of a whole file tree: SHA-256 over every file's path and
+/// digest, in path order. Identical trees give identical fingerprints.
+pub(super) fn tree_fingerprint(root: &FileTreeNode) -> String {
+    fn walk(node: &FileTreeNode, path: &str, lines: &mut Vec<String>) {
+        match node {
+            FileTreeNode::File { contents } => {
+                lines.push(format!("{}\0{}\n", path, sha256_hex(contents)));
+            }
+            FileTreeNode::Directory { files } => {
+                for (name, child) in files {
+                    let child_path = if path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{}/{}", path, name)
+                    };
+                    walk(child, &child_path, lines);
+                }
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    walk(root, "", &mut lines);
+    lines.sort();
+    sha256_hex(lines.concat().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sha256_hex;
+
+    #[test]
+    fn sha256_known_vectors() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+}
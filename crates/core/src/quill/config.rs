@@ -9,6 +9,7 @@ use time::format_description::well_known::Rfc3339;
 use time::{Date, OffsetDateTime};
 
 use crate::error::{Diagnostic, Severity};
+use crate::types::OutputFormat;
 use crate::value::QuillValue;
 
 use super::formats::DATE_FORMAT;
@@ -28,8 +29,12 @@ pub struct QuillConfig {
     /// Named, composable card-type schemas (parsed from the Quill.yaml
     /// `card_types:` section). Does not include `main`.
     pub card_types: Vec<CardSchema>,
-    /// Backend to use for rendering (e.g., "typst", "html")
+    /// Backend to use for rendering (e.g., "typst", "html"). Inferred from
+    /// the plate file extension when Quill.yaml does not declare it.
     pub backend: String,
+    /// Whether `backend` was declared in Quill.yaml rather than inferred.
+    #[serde(default)]
+    pub backend_declared: bool,
     /// Version of the Quillmark spec
     pub version: String,
     /// Author of the project
@@ -40,6 +45,31 @@ pub struct QuillConfig {
     pub example_markdown: Option<String>,
     /// Plate file (template)
     pub plate_file: Option<String>,
+    /// Minimal plate for rendering a document without the quill's page
+    /// layout (parsed from `quill.fragment_plate_file`)
+    #[serde(default)]
+    pub fragment_plate_file: Option<String>,
+    /// Markdown used as the document body when a document has none, with
+    /// `{{ field }}` placeholders (parsed from `quill.body_template_file`)
+    #[serde(default)]
+    pub body_template_file: Option<String>,
+    /// Output formats this quill is meant for (parsed from `quill.formats`).
+    /// `None` means every format the backend supports.
+    #[serde(default)]
+    pub formats: Option<Vec<OutputFormat>>,
+    /// Quill-relative directory holding bundled assets (parsed from
+    /// `quill.asset_dir`). Defaults to `assets`.
+    #[serde(default = "default_asset_dir")]
+    pub asset_dir: String,
+    /// Pinned SHA-256 digests of bundled assets, keyed by quill-relative path
+    /// (parsed from the top-level `checksums:` section). Lowercase hex.
+    #[serde(default)]
+    pub checksums: BTreeMap<String, String>,
+    /// Output formats each listed asset is limited to, keyed by
+    /// quill-relative path (parsed from the top-level `assets:` section).
+    /// Assets not listed apply to every format.
+    #[serde(default)]
+    pub asset_formats: BTreeMap<String, Vec<OutputFormat>>,
     /// Additional unstructured metadata
     #[serde(flatten)]
     pub metadata: HashMap<String, QuillValue>,
@@ -49,6 +79,33 @@ pub struct QuillConfig {
     pub backend_config: HashMap<String, QuillValue>,
 }
 
+/// Backend implied by a plate file's extension, for quills that do not
+/// declare `backend`.
+fn backend_for_plate(plate_file: &str) -> Option<&'static str> {
+    match std::path::Path::new(plate_file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("typ") => Some("typst"),
+        _ => None,
+    }
+}
+
+/// Output format named by a Quill.yaml entry such as `pdf`.
+fn parse_output_format(value: &serde_json::Value) -> Option<OutputFormat> {
+    match value.as_str().map(str::to_lowercase).as_deref() {
+        Some("pdf") => Some(OutputFormat::Pdf),
+        Some("svg") => Some(OutputFormat::Svg),
+        Some("png") => Some(OutputFormat::Png),
+        Some("txt") => Some(OutputFormat::Txt),
+        _ => None,
+    }
+}
+
+fn default_asset_dir() -> String {
+    "assets".to_string()
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct CardSchemaDef {
@@ -562,7 +619,9 @@ impl QuillConfig {
         chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
     }
 
-    fn is_valid_quill_name(name: &str) -> bool {
+    /// Whether `name` is accepted as `quill.name`: snake_case, or the
+    /// reserved `__default__`.
+    pub fn is_valid_quill_name(name: &str) -> bool {
         name == "__default__" || Self::is_snake_case_identifier(name)
     }
 
@@ -602,10 +661,18 @@ impl QuillConfig {
             .into());
         }
 
-        let backend = quill_section
-            .get("backend")
-            .and_then(|v| v.as_str())
-            .ok_or("Missing required 'backend' field in 'quill' section")?
+        let declared_backend = quill_section.get("backend").and_then(|v| v.as_str());
+        let backend_declared = declared_backend.is_some();
+        let backend = declared_backend
+            .or_else(|| {
+                quill_section
+                    .get("plate_file")
+                    .and_then(|v| v.as_str())
+                    .and_then(backend_for_plate)
+            })
+            .ok_or(
+                "Missing required 'backend' field in 'quill' section (it could not be inferred from plate_file)",
+            )?
             .to_string();
 
         let description = quill_section
@@ -659,6 +726,62 @@ impl QuillConfig {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let fragment_plate_file = quill_section
+            .get("fragment_plate_file")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let body_template_file = quill_section
+            .get("body_template_file")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let formats = match quill_section.get("formats") {
+            None => None,
+            Some(value) => {
+                let list = value
+                    .as_array()
+                    .ok_or("'formats' field in 'quill' section must be a list")?;
+                let mut formats = Vec::with_capacity(list.len());
+                for item in list {
+                    let format = parse_output_format(item).ok_or_else(|| {
+                        format!(
+                            "Invalid entry {} in 'quill.formats': expected one of pdf, svg, png, txt",
+                            item
+                        )
+                    })?;
+                    if !formats.contains(&format) {
+                        formats.push(format);
+                    }
+                }
+                if formats.is_empty() {
+                    return Err("'quill.formats' must list at least one output format".into());
+                }
+                Some(formats)
+            }
+        };
+
+        let asset_dir = match quill_section.get("asset_dir") {
+            None => default_asset_dir(),
+            Some(value) => {
+                let dir = value
+                    .as_str()
+                    .ok_or("'asset_dir' field in 'quill' section must be a string")?;
+                if !super::is_contained_path(dir) {
+                    return Err(format!(
+                        "Invalid 'asset_dir' '{}': must be a relative path inside the quill",
+                        dir
+                    )
+                    .into());
+                }
+                // Normalize `./media/` to `media` so it can prefix file lookups.
+                dir.split('/')
+                    .filter(|part| !part.is_empty() && *part != ".")
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }
+        };
+
         let ui_section: Option<UiContainerSchema> = quill_section
             .get("ui")
             .cloned()
@@ -677,6 +800,10 @@ impl QuillConfig {
                     && key != "example"
                     && key != "example_file"
                     && key != "plate_file"
+                    && key != "fragment_plate_file"
+                    && key != "body_template_file"
+                    && key != "formats"
+                    && key != "asset_dir"
                     && key != "ui"
                 {
                     metadata.insert(key.clone(), QuillValue::from_json(value.clone()));
@@ -801,6 +928,53 @@ impl QuillConfig {
             }
         }
 
+        // Extract [checksums] section (optional)
+        let mut checksums: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(checksums_val) = quill_yaml_val.get("checksums") {
+            let checksums_table = checksums_val
+                .as_object()
+                .ok_or("'checksums' section must be an object")?;
+            for (path, digest) in checksums_table {
+                let digest = digest
+                    .as_str()
+                    .filter(|d| d.len() == 64 && d.chars().all(|c| c.is_ascii_hexdigit()))
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid checksum for '{}': expected a 64-character hex SHA-256 digest",
+                            path
+                        )
+                    })?;
+                checksums.insert(path.clone(), digest.to_ascii_lowercase());
+            }
+        }
+
+        // Extract [assets] section (optional)
+        let mut asset_formats: BTreeMap<String, Vec<OutputFormat>> = BTreeMap::new();
+        if let Some(assets_val) = quill_yaml_val.get("assets") {
+            let assets_table = assets_val
+                .as_object()
+                .ok_or("'assets' section must be an object")?;
+            for (path, entry) in assets_table {
+                let list = entry
+                    .get("formats")
+                    .and_then(|formats| formats.as_array())
+                    .ok_or_else(|| format!("Asset '{}' must declare a 'formats' list", path))?;
+                let mut formats = Vec::with_capacity(list.len());
+                for item in list {
+                    let format = parse_output_format(item).ok_or_else(|| {
+                        format!(
+                            "Invalid format {} for asset '{}': expected one of pdf, svg, png, txt",
+                            item, path
+                        )
+                    })?;
+                    if !formats.contains(&format) {
+                        formats.push(format);
+                    }
+                }
+                asset_formats.insert(path.clone(), formats);
+            }
+        }
+
         Ok((
             QuillConfig {
                 name,
@@ -808,11 +982,18 @@ impl QuillConfig {
                 main,
                 card_types,
                 backend,
+                backend_declared,
                 version,
                 author,
                 example_file,
                 example_markdown: None,
                 plate_file,
+                fragment_plate_file,
+                body_template_file,
+                formats,
+                asset_dir,
+                checksums,
+                asset_formats,
                 metadata,
                 backend_config,
             },
@@ -13,6 +13,30 @@ impl QuillIgnore {
         Self { patterns }
     }
 
+    /// The patterns applied to a quill directory without a .quillignore:
+    /// version control, build output, dependency folders, and `*.bak`
+    /// backups (e.g. from `quillmark validate --fix`).
+    pub fn defaults() -> Self {
+        Self::new(vec![
+            ".git/".to_string(),
+            ".gitignore".to_string(),
+            ".quillignore".to_string(),
+            "target/".to_string(),
+            "node_modules/".to_string(),
+            "*.bak".to_string(),
+        ])
+    }
+
+    /// Add `patterns` to this set, e.g. to supplement [`QuillIgnore::defaults`].
+    pub fn with_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.patterns.extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
     /// Parse .quillignore content into patterns
     pub fn from_content(content: &str) -> Self {
         let patterns = content
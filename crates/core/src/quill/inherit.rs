@@ -0,0 +1,101 @@
+//! `quill.extends`: a quill layered over a base quill.
+//!
+//! A child quill declares `extends: ../base` in its `quill:` section. Its
+//! files are laid over the base's, child winning on path collisions, and
+//! the two Quill.yaml files merge section by section with child values
+//! taking precedence. Resolving the path is the loader's job (see
+//! `quillmark::Quillmark::quill_from_path`); this module only merges trees.
+
+use std::collections::HashMap;
+
+use crate::error::QuillError;
+
+use super::FileTreeNode;
+
+/// The base quill named by `quill.extends` in the tree's Quill.yaml, if any.
+pub fn extends_target(root: &FileTreeNode) -> Result<Option<String>, QuillError> {
+    let Some(bytes) = root.get_file("Quill.yaml") else {
+        return Ok(None);
+    };
+    let yaml = parse_yaml(bytes)?;
+    Ok(yaml
+        .get("quill")
+        .and_then(|quill| quill.get("extends"))
+        .and_then(|extends| extends.as_str())
+        .map(str::to_string))
+}
+
+/// Lay `child` over `base`.
+///
+/// Files present in both come from `child`, except Quill.yaml, whose
+/// mappings are merged recursively (child values win; lists are replaced,
+/// not concatenated). The merged Quill.yaml drops `quill.extends`.
+pub fn inherit(base: FileTreeNode, child: FileTreeNode) -> Result<FileTreeNode, QuillError> {
+    let config = match (base.get_file("Quill.yaml"), child.get_file("Quill.yaml")) {
+        (Some(base_yaml), Some(child_yaml)) => {
+            let mut merged = parse_yaml(base_yaml)?;
+            merge_yaml(&mut merged, parse_yaml(child_yaml)?);
+            if let Some(quill) = merged.get_mut("quill").and_then(|q| q.as_object_mut()) {
+                quill.remove("extends");
+            }
+            let text = serde_saphyr::to_string(&merged).map_err(|e| QuillError::InvalidConfig {
+                detail: format!("failed to write merged Quill.yaml: {}", e),
+            })?;
+            Some(text.into_bytes())
+        }
+        _ => None,
+    };
+
+    let mut tree = overlay(base, child);
+    if let (Some(contents), FileTreeNode::Directory { files }) = (config, &mut tree) {
+        files.insert("Quill.yaml".to_string(), FileTreeNode::File { contents });
+    }
+    Ok(tree)
+}
+
+fn parse_yaml(bytes: &[u8]) -> Result<serde_json::Value, QuillError> {
+    let text = std::str::from_utf8(bytes).map_err(|e| QuillError::InvalidConfig {
+        detail: format!("not valid UTF-8: {}", e),
+    })?;
+    serde_saphyr::from_str(text).map_err(|e| QuillError::InvalidConfig {
+        detail: format!("Failed to parse Quill.yaml: {}", e),
+    })
+}
+
+/// Merge `child` into `base`: objects recursively, anything else replaced.
+fn merge_yaml(base: &mut serde_json::Value, child: serde_json::Value) {
+    match (base, child) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(child)) => {
+            for (key, value) in child {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, child) => *base = child,
+    }
+}
+
+/// Files of both trees; `child` wins where both hold a file at one path.
+fn overlay(base: FileTreeNode, child: FileTreeNode) -> FileTreeNode {
+    match (base, child) {
+        (
+            FileTreeNode::Directory { files: base_files },
+            FileTreeNode::Directory { files: child_files },
+        ) => {
+            let mut files: HashMap<String, FileTreeNode> = base_files;
+            for (name, node) in child_files {
+                let merged = match files.remove(&name) {
+                    Some(existing) => overlay(existing, node),
+                    None => node,
+                };
+                files.insert(name, merged);
+            }
+            FileTreeNode::Directory { files }
+        }
+        (_, child) => child,
+    }
+}
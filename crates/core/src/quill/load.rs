@@ -1,10 +1,10 @@
 //! QuillSource loading and construction routines.
-use std::error::Error as StdError;
 use std::path::{Component, Path};
 
+use crate::error::QuillError;
 use crate::value::QuillValue;
 
-use super::{FileTreeNode, QuillConfig, QuillSource};
+use super::{checksum, FileTreeNode, QuillConfig, QuillSource};
 
 impl QuillSource {
     /// Create a QuillSource from a tree structure.
@@ -23,28 +23,38 @@ impl QuillSource {
     /// - Quill.yaml is not found in the file tree
     /// - Quill.yaml is not valid UTF-8 or YAML
     /// - The plate file specified in Quill.yaml is not found or not valid UTF-8
+    /// - An asset pinned in `checksums:` is missing or its SHA-256 differs
+    /// - `quill.extends` is still set (the base was not merged in; see
+    ///   [`super::inherit`])
     /// - Validation fails
-    pub fn from_tree(root: FileTreeNode) -> Result<Self, Box<dyn StdError + Send + Sync>> {
+    pub fn from_tree(root: FileTreeNode) -> Result<Self, QuillError> {
+        if let Some(base) = super::extends_target(&root)? {
+            return Err(QuillError::UnresolvedExtends { base });
+        }
+
         // Read Quill.yaml
         let quill_yaml_bytes = root
             .get_file("Quill.yaml")
-            .ok_or("Quill.yaml not found in file tree")?;
+            .ok_or(QuillError::MissingConfig)?;
 
-        let quill_yaml_content = String::from_utf8(quill_yaml_bytes.to_vec())
-            .map_err(|e| format!("Quill.yaml is not valid UTF-8: {}", e))?;
+        let quill_yaml_content = String::from_utf8(quill_yaml_bytes.to_vec()).map_err(|e| {
+            QuillError::InvalidConfig {
+                detail: format!("not valid UTF-8: {}", e),
+            }
+        })?;
 
         // Parse YAML into QuillConfig
-        let config = QuillConfig::from_yaml(&quill_yaml_content)?;
+        let config =
+            QuillConfig::from_yaml(&quill_yaml_content).map_err(|e| QuillError::InvalidConfig {
+                detail: e.to_string(),
+            })?;
 
         // Construct QuillSource from QuillConfig
         Self::from_config(config, root)
     }
 
     /// Create a QuillSource from a QuillConfig and file tree.
-    fn from_config(
-        mut config: QuillConfig,
-        root: FileTreeNode,
-    ) -> Result<Self, Box<dyn StdError + Send + Sync>> {
+    fn from_config(mut config: QuillConfig, root: FileTreeNode) -> Result<Self, QuillError> {
         // Build metadata from config
         let mut metadata = config.metadata.clone();
 
@@ -77,19 +87,9 @@ impl QuillSource {
         }
 
         // Read the plate content from plate file (if specified)
-        let plate_content: Option<String> = if let Some(ref plate_file_name) = config.plate_file {
-            let plate_bytes = root.get_file(plate_file_name).ok_or_else(|| {
-                format!("Plate file '{}' not found in file tree", plate_file_name)
-            })?;
-
-            let content = String::from_utf8(plate_bytes.to_vec()).map_err(|e| {
-                format!("Plate file '{}' is not valid UTF-8: {}", plate_file_name, e)
-            })?;
-            Some(content)
-        } else {
-            // No plate file specified
-            None
-        };
+        let plate_content = read_plate(&root, config.plate_file.as_deref())?;
+        let fragment_plate_content = read_plate(&root, config.fragment_plate_file.as_deref())?;
+        let body_template = read_body_template(&root, config.body_template_file.as_deref())?;
 
         // Read the markdown example content if specified, or check for default "example.md"
         let example_content = if let Some(ref example_file_name) = config.example_file {
@@ -99,52 +99,124 @@ impl QuillSource {
                     .components()
                     .any(|c| matches!(c, Component::ParentDir | Component::Prefix(_)))
             {
-                return Err(format!(
-                    "Example file '{}' is outside the quill directory",
-                    example_file_name
-                )
-                .into());
+                return Err(QuillError::InvalidExample {
+                    file: example_file_name.clone(),
+                    detail: format!(
+                        "Example file '{}' is outside the quill directory",
+                        example_file_name
+                    ),
+                });
             }
 
-            let bytes = root.get_file(example_file_name).ok_or_else(|| {
-                format!(
-                    "Example file '{}' referenced in Quill.yaml not found",
-                    example_file_name
-                )
-            })?;
-            Some(String::from_utf8(bytes.to_vec()).map_err(|e| {
-                format!(
-                    "Example file '{}' is not valid UTF-8: {}",
-                    example_file_name, e
-                )
-            })?)
+            let bytes =
+                root.get_file(example_file_name)
+                    .ok_or_else(|| QuillError::InvalidExample {
+                        file: example_file_name.clone(),
+                        detail: format!(
+                            "Example file '{}' referenced in Quill.yaml not found",
+                            example_file_name
+                        ),
+                    })?;
+            Some(
+                String::from_utf8(bytes.to_vec()).map_err(|e| QuillError::InvalidExample {
+                    file: example_file_name.clone(),
+                    detail: format!(
+                        "Example file '{}' is not valid UTF-8: {}",
+                        example_file_name, e
+                    ),
+                })?,
+            )
         } else if root.file_exists("example.md") {
             // Smart default: use example.md if it exists
             let bytes = root
                 .get_file("example.md")
                 .expect("invariant violation: file_exists(example.md) but get_file returned None");
-            Some(String::from_utf8(bytes.to_vec()).map_err(|e| {
-                format!(
-                    "Default example file 'example.md' is not valid UTF-8: {}",
-                    e
-                )
-            })?)
+            Some(
+                String::from_utf8(bytes.to_vec()).map_err(|e| QuillError::InvalidExample {
+                    file: "example.md".to_string(),
+                    detail: format!(
+                        "Default example file 'example.md' is not valid UTF-8: {}",
+                        e
+                    ),
+                })?,
+            )
         } else {
             None
         };
 
         config.example_markdown = example_content.clone();
 
+        checksum::verify_checksums(&config.checksums, &root)?;
+        if let Some(file) = config
+            .asset_formats
+            .keys()
+            .find(|file| !root.file_exists(file))
+        {
+            return Err(QuillError::MissingAsset { file: file.clone() });
+        }
+
         let source = QuillSource {
             metadata,
             name: config.name.clone(),
             backend_id: config.backend.clone(),
             plate: plate_content,
+            fragment_plate: fragment_plate_content,
+            body_template,
             example: example_content,
             config,
             files: root,
+            fingerprint: Default::default(),
         };
 
         Ok(source)
     }
 }
+
+/// Read a plate file declared in Quill.yaml, if one is declared.
+fn read_plate(root: &FileTreeNode, file: Option<&str>) -> Result<Option<String>, QuillError> {
+    let Some(file) = file else {
+        return Ok(None);
+    };
+    let bytes = root
+        .get_file(file)
+        .ok_or_else(|| QuillError::MissingPlate {
+            file: file.to_string(),
+        })?;
+    String::from_utf8(bytes.to_vec())
+        .map(Some)
+        .map_err(|_| QuillError::PlateNotUtf8 {
+            file: file.to_string(),
+        })
+}
+
+/// Read the body template declared in Quill.yaml, if one is declared.
+fn read_body_template(
+    root: &FileTreeNode,
+    file: Option<&str>,
+) -> Result<Option<String>, QuillError> {
+    let Some(file) = file else {
+        return Ok(None);
+    };
+    let invalid = |detail: String| QuillError::InvalidBodyTemplate {
+        file: file.to_string(),
+        detail,
+    };
+    if !super::is_contained_path(file) {
+        return Err(invalid(format!(
+            "Body template '{}' is outside the quill directory",
+            file
+        )));
+    }
+    let bytes = root.get_file(file).ok_or_else(|| {
+        invalid(format!(
+            "Body template '{}' referenced in Quill.yaml not found",
+            file
+        ))
+    })?;
+    String::from_utf8(bytes.to_vec()).map(Some).map_err(|e| {
+        invalid(format!(
+            "Body template '{}' is not valid UTF-8: {}",
+            file, e
+        ))
+    })
+}
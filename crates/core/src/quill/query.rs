@@ -3,7 +3,58 @@ use std::path::{Path, PathBuf};
 
 use super::{FileTreeNode, QuillSource};
 
+/// A file or directory in a quill, yielded by [`QuillSource::walk`].
+#[derive(Debug, Clone)]
+pub struct FileEntry<'a> {
+    /// Path relative to the quill root.
+    pub path: PathBuf,
+    /// The file or directory node at `path`.
+    pub node: &'a FileTreeNode,
+}
+
+impl<'a> FileEntry<'a> {
+    /// Returns `true` for files, `false` for directories.
+    pub fn is_file(&self) -> bool {
+        matches!(self.node, FileTreeNode::File { .. })
+    }
+
+    /// File contents, or `None` for a directory.
+    pub fn contents(&self) -> Option<&'a [u8]> {
+        match self.node {
+            FileTreeNode::File { contents } => Some(contents),
+            FileTreeNode::Directory { .. } => None,
+        }
+    }
+}
+
 impl QuillSource {
+    /// Every file and directory in the quill except the root, in sorted path
+    /// order (a directory comes before its contents).
+    pub fn walk(&self) -> impl Iterator<Item = FileEntry<'_>> {
+        fn collect<'a>(node: &'a FileTreeNode, path: &Path, entries: &mut Vec<FileEntry<'a>>) {
+            if let FileTreeNode::Directory { files } = node {
+                for (name, child) in files {
+                    let child_path = path.join(name);
+                    collect(child, &child_path, entries);
+                    entries.push(FileEntry {
+                        path: child_path,
+                        node: child,
+                    });
+                }
+            }
+        }
+
+        let mut entries = Vec::new();
+        collect(&self.files, Path::new(""), &mut entries);
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries.into_iter()
+    }
+
+    /// Like [`QuillSource::walk`], skipping directories.
+    pub fn walk_files(&self) -> impl Iterator<Item = FileEntry<'_>> {
+        self.walk().filter(FileEntry::is_file)
+    }
+
     /// Get file contents by path (relative to quill root)
     pub fn get_file<P: AsRef<Path>>(&self, path: P) -> Option<&[u8]> {
         self.files.get_file(path)
@@ -8,6 +8,21 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
+fn file(contents: impl AsRef<[u8]>) -> FileTreeNode {
+    FileTreeNode::File {
+        contents: contents.as_ref().to_vec(),
+    }
+}
+
+fn dir(entries: Vec<(&str, FileTreeNode)>) -> FileTreeNode {
+    FileTreeNode::Directory {
+        files: entries
+            .into_iter()
+            .map(|(name, node)| (name.to_string(), node))
+            .collect(),
+    }
+}
+
 /// Test helper: recursively load a directory as a FileTreeNode.
 fn load_tree(path: &Path) -> Result<FileTreeNode, Box<dyn StdError + Send + Sync>> {
     let default_ignore = QuillIgnore::new(vec![
@@ -67,7 +82,7 @@ fn load_dir(
 /// Test helper: filesystem equivalent of the old `Quill::from_path`.
 fn load_from_path<P: AsRef<Path>>(path: P) -> Result<QuillSource, Box<dyn StdError + Send + Sync>> {
     let tree = load_tree(path.as_ref())?;
-    QuillSource::from_tree(tree)
+    Ok(QuillSource::from_tree(tree)?)
 }
 
 #[test]
@@ -214,6 +229,91 @@ fn test_find_files_pattern() {
     assert!(typ_files.contains(&PathBuf::from("plate.typ")));
 }
 
+#[test]
+fn test_inherit_layers_child_over_base() {
+    let base = dir(vec![
+        (
+            "Quill.yaml",
+            file("quill:\n  name: base\n  version: \"1.0\"\n  backend: typst\n  plate_file: plate.typ\n  description: Base\n"),
+        ),
+        ("plate.typ", file("base")),
+        ("assets", dir(vec![("a.txt", file("base a")), ("b.txt", file("base b"))])),
+    ]);
+    let child = dir(vec![
+        (
+            "Quill.yaml",
+            file("quill:\n  name: child\n  extends: ../base\n  description: Child\n"),
+        ),
+        ("plate.typ", file("child")),
+        ("assets", dir(vec![("b.txt", file("child b"))])),
+    ]);
+
+    assert_eq!(extends_target(&child).unwrap().as_deref(), Some("../base"));
+    match QuillSource::from_tree(child.clone()) {
+        Err(QuillError::UnresolvedExtends { base }) => assert_eq!(base, "../base"),
+        other => panic!("expected UnresolvedExtends, got {:?}", other.map(|_| ())),
+    }
+
+    let merged = inherit(base, child).unwrap();
+    assert_eq!(extends_target(&merged).unwrap(), None);
+    assert_eq!(merged.get_file("assets/a.txt"), Some(&b"base a"[..]));
+    assert_eq!(merged.get_file("assets/b.txt"), Some(&b"child b"[..]));
+
+    let source = QuillSource::from_tree(merged).unwrap();
+    assert_eq!(source.name(), "child");
+    assert_eq!(source.plate(), Some("child"));
+    assert_eq!(source.config().version, "1.0");
+}
+
+#[test]
+fn test_walk_sorted_and_walk_files_skips_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_dir = temp_dir.path();
+    fs::write(
+        quill_dir.join("Quill.yaml"),
+        "quill:\n  name: \"test\"\n  version: \"1.0\"\n  backend: \"typst\"\n  plate_file: \"plate.typ\"\n  description: \"Test quill\"",
+    )
+    .unwrap();
+    fs::write(quill_dir.join("plate.typ"), "template").unwrap();
+    fs::create_dir_all(quill_dir.join("assets/fonts")).unwrap();
+    fs::write(quill_dir.join("assets/logo.png"), "png data").unwrap();
+    fs::write(quill_dir.join("assets/fonts/font.ttf"), "font data").unwrap();
+
+    let quill = load_from_path(quill_dir).unwrap();
+
+    let all: Vec<(PathBuf, bool)> = quill
+        .walk()
+        .map(|e| (e.path.clone(), e.is_file()))
+        .collect();
+    assert_eq!(
+        all,
+        vec![
+            (PathBuf::from("Quill.yaml"), true),
+            (PathBuf::from("assets"), false),
+            (PathBuf::from("assets/fonts"), false),
+            (PathBuf::from("assets/fonts/font.ttf"), true),
+            (PathBuf::from("assets/logo.png"), true),
+            (PathBuf::from("plate.typ"), true),
+        ]
+    );
+
+    let files: Vec<PathBuf> = quill.walk_files().map(|e| e.path).collect();
+    assert_eq!(
+        files,
+        vec![
+            PathBuf::from("Quill.yaml"),
+            PathBuf::from("assets/fonts/font.ttf"),
+            PathBuf::from("assets/logo.png"),
+            PathBuf::from("plate.typ"),
+        ]
+    );
+    let plate = quill
+        .walk_files()
+        .find(|e| e.path == Path::new("plate.typ"))
+        .unwrap();
+    assert_eq!(plate.contents(), Some(&b"template"[..]));
+}
+
 #[test]
 fn test_new_standardized_yaml_format() {
     let temp_dir = TempDir::new().unwrap();
@@ -405,6 +505,221 @@ fn test_from_tree() {
     assert!(quill.metadata.contains_key("description"));
 }
 
+#[test]
+fn test_from_tree_missing_plate_is_typed() {
+    let quill_yaml = "quill:\n  name: missing_plate\n  version: \"1.0\"\n  backend: typst\n  plate_file: plate.typ\n  description: Missing plate\n";
+    let mut root_files = HashMap::new();
+    root_files.insert(
+        "Quill.yaml".to_string(),
+        FileTreeNode::File {
+            contents: quill_yaml.as_bytes().to_vec(),
+        },
+    );
+
+    let err = QuillSource::from_tree(FileTreeNode::Directory { files: root_files }).unwrap_err();
+    match &err {
+        crate::QuillError::MissingPlate { file } => assert_eq!(file, "plate.typ"),
+        other => panic!("expected MissingPlate, got {:?}", other),
+    }
+    let diag = err.to_diagnostic();
+    assert_eq!(diag.code.as_deref(), Some("quill::missing_plate"));
+    assert!(diag.hint.unwrap().contains("plate.typ"));
+}
+
+fn checksum_tree(checksums: &str) -> FileTreeNode {
+    let quill_yaml = format!(
+        "quill:\n  name: pinned\n  version: \"1.0\"\n  backend: typst\n  description: Pinned assets\n{}",
+        checksums
+    );
+    let mut root_files = HashMap::new();
+    for (name, contents) in [
+        ("Quill.yaml", quill_yaml.into_bytes()),
+        ("logo.png", b"abc".to_vec()),
+        ("font.ttf", b"unpinned".to_vec()),
+    ] {
+        root_files.insert(name.to_string(), FileTreeNode::File { contents });
+    }
+    FileTreeNode::Directory { files: root_files }
+}
+
+#[test]
+fn test_from_tree_matching_checksum_loads() {
+    let tree = checksum_tree(
+        "checksums:\n  logo.png: BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD\n",
+    );
+    let quill = QuillSource::from_tree(tree).unwrap();
+    assert_eq!(
+        quill.config.checksums["logo.png"],
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_from_tree_checksum_mismatch_is_typed() {
+    let tree = checksum_tree(
+        "checksums:\n  logo.png: ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\n",
+    );
+    let err = QuillSource::from_tree(tree).unwrap_err();
+    match &err {
+        crate::QuillError::ChecksumMismatch { file, actual, .. } => {
+            assert_eq!(file, "logo.png");
+            assert_eq!(
+                actual,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            );
+        }
+        other => panic!("expected ChecksumMismatch, got {:?}", other),
+    }
+    assert_eq!(
+        err.to_diagnostic().code.as_deref(),
+        Some("quill::checksum_mismatch")
+    );
+
+    let missing = checksum_tree(
+        "checksums:\n  gone.png: ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n",
+    );
+    assert!(matches!(
+        QuillSource::from_tree(missing).unwrap_err(),
+        crate::QuillError::MissingAsset { .. }
+    ));
+
+    let malformed = checksum_tree("checksums:\n  logo.png: not-a-digest\n");
+    assert!(matches!(
+        QuillSource::from_tree(malformed).unwrap_err(),
+        crate::QuillError::InvalidConfig { .. }
+    ));
+}
+
+#[test]
+fn test_from_tree_asset_formats() {
+    let tree = checksum_tree("assets:\n  font.ttf:\n    formats: [PDF, pdf, png]\n");
+    let quill = QuillSource::from_tree(tree).unwrap();
+    assert_eq!(
+        quill.config.asset_formats["font.ttf"],
+        vec![OutputFormat::Pdf, OutputFormat::Png]
+    );
+    assert!(quill.asset_applies_to("font.ttf", Some(OutputFormat::Pdf)));
+    assert!(!quill.asset_applies_to("font.ttf", Some(OutputFormat::Svg)));
+    assert!(quill.asset_applies_to("font.ttf", None));
+    assert!(quill.asset_applies_to("logo.png", Some(OutputFormat::Svg)));
+
+    let missing = checksum_tree("assets:\n  gone.ttf:\n    formats: [pdf]\n");
+    assert!(matches!(
+        QuillSource::from_tree(missing).unwrap_err(),
+        crate::QuillError::MissingAsset { .. }
+    ));
+
+    let bad_format = checksum_tree("assets:\n  font.ttf:\n    formats: [docx]\n");
+    assert!(matches!(
+        QuillSource::from_tree(bad_format).unwrap_err(),
+        crate::QuillError::InvalidConfig { .. }
+    ));
+}
+
+#[test]
+fn test_from_tree_unlisted_asset_is_unchecked() {
+    // Only logo.png is pinned; font.ttf is loaded without verification,
+    // and a quill with no `checksums:` section checks nothing.
+    let pinned = checksum_tree(
+        "checksums:\n  logo.png: ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n",
+    );
+    let quill = QuillSource::from_tree(pinned).unwrap();
+    assert!(!quill.config.checksums.contains_key("font.ttf"));
+
+    let quill = QuillSource::from_tree(checksum_tree("")).unwrap();
+    assert!(quill.config.checksums.is_empty());
+}
+
+#[test]
+fn test_fingerprint_tracks_file_contents() {
+    let a = QuillSource::from_tree(checksum_tree("")).unwrap();
+    let b = QuillSource::from_tree(checksum_tree("")).unwrap();
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert_eq!(a.fingerprint().len(), 64);
+
+    let mut tree = checksum_tree("");
+    if let FileTreeNode::Directory { files } = &mut tree {
+        files.insert(
+            "logo.png".to_string(),
+            FileTreeNode::File {
+                contents: b"abd".to_vec(),
+            },
+        );
+    }
+    let edited = QuillSource::from_tree(tree).unwrap();
+    assert_ne!(a.fingerprint(), edited.fingerprint());
+}
+
+#[test]
+fn test_asset_dir_defaults_to_assets() {
+    let quill = QuillSource::from_tree(checksum_tree("")).unwrap();
+    assert_eq!(quill.asset_dir(), "assets");
+    assert_eq!(
+        quill.asset_path("fonts/a.ttf").unwrap(),
+        std::path::Path::new("assets/fonts/a.ttf")
+    );
+    assert!(!quill.metadata.contains_key("asset_dir"));
+}
+
+#[test]
+fn test_asset_dir_custom() {
+    let quill = QuillSource::from_tree(checksum_tree("  asset_dir: ./media/\n")).unwrap();
+    assert_eq!(quill.asset_dir(), "media");
+    assert_eq!(
+        quill.asset_path("./logo.png").unwrap(),
+        std::path::Path::new("media/logo.png")
+    );
+
+    for bad in ["../shared", "/etc", "media/../..", "."] {
+        let tree = checksum_tree(&format!("  asset_dir: \"{}\"\n", bad));
+        assert!(
+            matches!(
+                QuillSource::from_tree(tree).unwrap_err(),
+                crate::QuillError::InvalidConfig { .. }
+            ),
+            "asset_dir {:?} should be rejected",
+            bad
+        );
+    }
+}
+
+#[test]
+fn test_asset_path_rejects_traversal() {
+    let quill = QuillSource::from_tree(checksum_tree("")).unwrap();
+    for bad in ["../Quill.yaml", "fonts/../../plate.typ", "/etc/passwd", ""] {
+        let err = quill.asset_path(bad).unwrap_err();
+        assert!(
+            matches!(err, crate::QuillError::InvalidAssetPath { .. }),
+            "{:?} should be rejected",
+            bad
+        );
+        assert_eq!(
+            err.to_diagnostic().code.as_deref(),
+            Some("quill::invalid_asset_path")
+        );
+    }
+}
+
+#[test]
+fn test_from_tree_malformed_yaml_is_typed() {
+    let mut root_files = HashMap::new();
+    root_files.insert(
+        "Quill.yaml".to_string(),
+        FileTreeNode::File {
+            contents: b"quill: [unclosed".to_vec(),
+        },
+    );
+
+    let err = QuillSource::from_tree(FileTreeNode::Directory { files: root_files }).unwrap_err();
+    assert!(matches!(err, crate::QuillError::InvalidConfig { .. }));
+
+    let err = QuillSource::from_tree(FileTreeNode::Directory {
+        files: HashMap::new(),
+    })
+    .unwrap_err();
+    assert!(matches!(err, crate::QuillError::MissingConfig));
+}
+
 #[test]
 fn test_from_tree_with_template() {
     let mut root_files = HashMap::new();
@@ -497,6 +812,35 @@ fn test_from_tree_structure_direct() {
     assert!(quill.file_exists("plate.typ"));
 }
 
+#[test]
+fn test_insert_rejects_file_directory_conflict() {
+    let mut root = dir(vec![]);
+    root.insert("Quill.yaml", file(b"quill")).unwrap();
+    root.insert("src/main.typ", file(b"main")).unwrap();
+    root.insert("src/lib/util.typ", file(b"util")).unwrap();
+    root.insert("src/main.typ", file(b"replaced")).unwrap();
+    root.insert("src", dir(vec![])).unwrap();
+    assert_eq!(root.get_file("Quill.yaml"), Some(&b"quill"[..]));
+    assert!(root.dir_exists("src"));
+
+    let mut root = dir(vec![]);
+    root.insert("src/main.typ", file(b"main")).unwrap();
+    let err = root.insert("src", file(b"src")).unwrap_err();
+    assert_eq!(err.to_string(), "Path 'src' is both a file and a directory");
+    assert_eq!(root.get_file("src/main.typ"), Some(&b"main"[..]));
+
+    let mut root = dir(vec![]);
+    root.insert("assets/logo", file(b"logo")).unwrap();
+    let err = root
+        .insert("assets/logo/small.png", file(b"png"))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Path 'assets/logo' is both a file and a directory"
+    );
+    assert_eq!(root.get_file("assets/logo"), Some(&b"logo"[..]));
+}
+
 #[test]
 fn test_dir_exists_and_list_apis() {
     let mut root_files = HashMap::new();
@@ -860,6 +1204,33 @@ quill:
     assert_eq!(config.example_file, Some("examples/basic.md".to_string()));
 }
 
+#[test]
+fn test_quill_config_parses_formats() {
+    let yaml_content = r#"
+quill:
+  name: test_formats
+  version: "1.0"
+  backend: typst
+  description: Test formats parsing
+  formats: [PDF, pdf, png]
+"#;
+
+    let config = QuillConfig::from_yaml(yaml_content).unwrap();
+    assert_eq!(
+        config.formats,
+        Some(vec![crate::OutputFormat::Pdf, crate::OutputFormat::Png])
+    );
+    assert!(!config.metadata.contains_key("formats"));
+
+    let invalid = yaml_content.replace("[PDF, pdf, png]", "[pdf, docx]");
+    let err = QuillConfig::from_yaml(&invalid).unwrap_err();
+    assert!(err.to_string().contains("quill.formats"));
+
+    let empty = yaml_content.replace("[PDF, pdf, png]", "[]");
+    let err = QuillConfig::from_yaml(&empty).unwrap_err();
+    assert!(err.to_string().contains("at least one output format"));
+}
+
 #[test]
 fn test_quill_from_path_rejects_example_traversal() {
     let temp_dir = TempDir::new().unwrap();
@@ -904,6 +1275,45 @@ fn test_quill_from_path_errors_when_explicit_example_missing() {
         .contains("referenced in Quill.yaml not found"));
 }
 
+#[test]
+fn test_backend_declared_or_inferred_from_plate() {
+    let quill = |backend: &str, plate: &str| {
+        let yaml = format!(
+            "quill:\n  name: test\n  version: \"1.0\"\n{}  plate_file: {}\n  description: Test\n",
+            backend, plate
+        );
+        let mut files = HashMap::new();
+        files.insert(
+            "Quill.yaml".to_string(),
+            FileTreeNode::File {
+                contents: yaml.into_bytes(),
+            },
+        );
+        files.insert(
+            plate.to_string(),
+            FileTreeNode::File {
+                contents: b"plate".to_vec(),
+            },
+        );
+        QuillSource::from_tree(FileTreeNode::Directory { files })
+    };
+
+    let declared = quill("  backend: typst\n", "plate.typ").unwrap();
+    assert_eq!(declared.backend_id(), "typst");
+    assert_eq!(declared.declared_backend(), Some("typst"));
+
+    let inferred = quill("", "main.typ").unwrap();
+    assert_eq!(inferred.backend_id(), "typst");
+    assert_eq!(inferred.declared_backend(), None);
+
+    let Err(err) = quill("", "form.json") else {
+        panic!("expected an error for an unknown plate extension");
+    };
+    assert!(err
+        .to_string()
+        .contains("could not be inferred from plate_file"));
+}
+
 #[test]
 fn test_quill_config_missing_required_fields() {
     // Test that missing required fields result in error
@@ -111,7 +111,9 @@ impl FileTreeNode {
         }
     }
 
-    /// Insert a file or directory at the given path
+    /// Insert a file or directory at the given path, replacing a node of the
+    /// same kind. Fails when the path, or one of its parents, already exists
+    /// as the other kind (e.g. `src` as a file and `src/main.typ` as a file).
     pub fn insert<P: AsRef<Path>>(
         &mut self,
         path: P,
@@ -147,9 +149,17 @@ impl FileTreeNode {
             return Err("Cannot insert at root path".into());
         }
 
+        let conflict = |depth: usize| -> Box<dyn StdError + Send + Sync> {
+            format!(
+                "Path '{}' is both a file and a directory",
+                components[..=depth].join("/")
+            )
+            .into()
+        };
+
         // Navigate to parent directory, creating directories as needed
         let mut current_node = self;
-        for component in &components[..components.len() - 1] {
+        for (depth, component) in components[..components.len() - 1].iter().enumerate() {
             match current_node {
                 FileTreeNode::Directory { files } => {
                     current_node =
@@ -158,6 +168,9 @@ impl FileTreeNode {
                             .or_insert_with(|| FileTreeNode::Directory {
                                 files: HashMap::new(),
                             });
+                    if matches!(current_node, FileTreeNode::File { .. }) {
+                        return Err(conflict(depth));
+                    }
                 }
                 FileTreeNode::File { .. } => {
                     return Err("Cannot traverse into a file".into());
@@ -169,6 +182,11 @@ impl FileTreeNode {
         let filename = &components[components.len() - 1];
         match current_node {
             FileTreeNode::Directory { files } => {
+                if let Some(existing) = files.get(filename) {
+                    if std::mem::discriminant(existing) != std::mem::discriminant(&node) {
+                        return Err(conflict(components.len() - 1));
+                    }
+                }
                 files.insert(filename.clone(), node);
                 Ok(())
             }
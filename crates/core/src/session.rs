@@ -1,6 +1,6 @@
 use std::any::Any;
 
-use crate::{Diagnostic, RenderError, RenderOptions, RenderResult};
+use crate::{Diagnostic, RenderError, RenderOptions, RenderResult, Severity};
 
 /// Backend-specific session implementation.
 ///
@@ -12,6 +12,22 @@ pub trait SessionHandle: Any + Send + Sync {
     fn render(&self, opts: &RenderOptions) -> Result<RenderResult, RenderError>;
     fn page_count(&self) -> usize;
     fn as_any(&self) -> &dyn Any;
+
+    /// Append the pages of `other` after this session's pages.
+    ///
+    /// Backends that cannot merge sessions keep the default, which errors.
+    fn append(&mut self, other: &dyn SessionHandle) -> Result<(), RenderError> {
+        let _ = other;
+        Err(RenderError::UnsupportedBackend {
+            diag: Box::new(
+                Diagnostic::new(
+                    Severity::Error,
+                    "This backend does not support combining render sessions".to_string(),
+                )
+                .with_code("session::append_not_supported".to_string()),
+            ),
+        })
+    }
 }
 
 /// Opaque, backend-backed iterative render session.
@@ -61,7 +77,38 @@ impl RenderSession {
         &self.warnings
     }
 
+    /// Append the pages of `other` after this session's pages, merging its
+    /// session-level warnings into this one.
+    ///
+    /// Both sessions must come from the same backend.
+    pub fn append(&mut self, other: RenderSession) -> Result<(), RenderError> {
+        self.inner.append(&*other.inner)?;
+        self.warnings.extend(other.warnings);
+        Ok(())
+    }
+
     pub fn render(&self, opts: &RenderOptions) -> Result<RenderResult, RenderError> {
+        if let Some(max_pages) = opts.max_pages {
+            let page_count = self.page_count();
+            if page_count > max_pages {
+                return Err(RenderError::TooManyPages {
+                    diag: Box::new(
+                        Diagnostic::new(
+                            Severity::Error,
+                            format!(
+                                "Document has {} pages, exceeding the limit of {}",
+                                page_count, max_pages
+                            ),
+                        )
+                        .with_code("render::too_many_pages".to_string())
+                        .with_hint(format!(
+                            "Shorten the document to at most {} pages",
+                            max_pages
+                        )),
+                    ),
+                });
+            }
+        }
         let mut result = self.inner.render(opts)?;
         result.warnings.extend(self.warnings.iter().cloned());
         Ok(result)
@@ -0,0 +1,110 @@
+//! Conformance checks for [`Backend`] implementations.
+//!
+//! Enabled with the `testing` feature. Backend authors call
+//! [`assert_backend_conformance`] from their own test suites to check the
+//! edge cases every backend must handle.
+
+use std::collections::HashMap;
+
+use crate::{Backend, FileTreeNode, OutputFormat, QuillSource, RenderError, RenderOptions};
+
+const ALL_FORMATS: &[OutputFormat] = &[
+    OutputFormat::Txt,
+    OutputFormat::Svg,
+    OutputFormat::Pdf,
+    OutputFormat::Png,
+];
+
+/// Drive `backend` through the standard conformance battery, panicking with a
+/// descriptive message on the first failure.
+///
+/// Checks that:
+/// - `supported_formats()` is non-empty;
+/// - a trivial document (empty plate, no fields) opens and renders in every
+///   supported format, producing artifacts tagged with that format;
+/// - rendering without an explicit format yields a supported format;
+/// - every unsupported format fails with [`RenderError::FormatNotSupported`].
+pub fn assert_backend_conformance(backend: &dyn Backend) {
+    let id = backend.id();
+    let formats = backend.supported_formats();
+    assert!(
+        !formats.is_empty(),
+        "backend '{}' declares no supported formats",
+        id
+    );
+
+    let source = trivial_source(id);
+    let data = serde_json::json!({ "QUILL": "conformance", "BODY": "", "CARDS": [] });
+    let session = backend
+        .open("", &source, &data)
+        .unwrap_or_else(|e| panic!("backend '{}' failed to open trivial content: {}", id, e));
+
+    for &format in formats {
+        let result = session
+            .render(&RenderOptions {
+                output_format: Some(format),
+                ..Default::default()
+            })
+            .unwrap_or_else(|e| panic!("backend '{}' failed to render {:?}: {}", id, format, e));
+        assert_eq!(
+            result.output_format, format,
+            "backend '{}' reported the wrong output format",
+            id
+        );
+        assert!(
+            !result.artifacts.is_empty(),
+            "backend '{}' produced no {:?} artifacts",
+            id,
+            format
+        );
+        assert!(
+            result.artifacts.iter().all(|a| a.output_format == format),
+            "backend '{}' produced artifacts not tagged {:?}",
+            id,
+            format
+        );
+    }
+
+    let defaulted = session
+        .render(&RenderOptions::default())
+        .unwrap_or_else(|e| panic!("backend '{}' failed to render with no format: {}", id, e));
+    assert!(
+        formats.contains(&defaulted.output_format),
+        "backend '{}' defaulted to unsupported format {:?}",
+        id,
+        defaulted.output_format
+    );
+
+    for &format in ALL_FORMATS.iter().filter(|f| !formats.contains(f)) {
+        let result = session.render(&RenderOptions {
+            output_format: Some(format),
+            ..Default::default()
+        });
+        match result {
+            Err(RenderError::FormatNotSupported { .. }) => {}
+            other => panic!(
+                "backend '{}' should reject {:?} with FormatNotSupported, got {:?}",
+                id,
+                format,
+                other.map(|r| r.output_format)
+            ),
+        }
+    }
+}
+
+/// A minimal quill declaring `backend_id`, with no plate or fields.
+fn trivial_source(backend_id: &str) -> QuillSource {
+    let quill_yaml = format!(
+        "quill:\n  name: conformance\n  version: \"1.0\"\n  backend: \"{}\"\n  description: Backend conformance quill\n",
+        backend_id
+    );
+    let mut files = HashMap::new();
+    files.insert(
+        "Quill.yaml".to_string(),
+        FileTreeNode::File {
+            contents: quill_yaml.into_bytes(),
+        },
+    );
+    QuillSource::from_tree(FileTreeNode::Directory { files })
+        .unwrap_or_else(|e| panic!("conformance quill failed to load: {}", e))
+}
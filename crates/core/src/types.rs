@@ -1,7 +1,7 @@
 //! Core types for rendering and output formats.
 
 /// Output formats supported by backends.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum OutputFormat {
     /// Plain text output
     Txt,
@@ -13,17 +13,54 @@ pub enum OutputFormat {
     Png,
 }
 
+/// PDF/A archival conformance levels a backend can enforce on PDF output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum PdfStandard {
+    /// PDF/A-1b (basic conformance, PDF 1.4)
+    A1b,
+    /// PDF/A-2b (basic conformance, PDF 1.7)
+    A2b,
+    /// PDF/A-2u (PDF/A-2b with Unicode-mapped text)
+    A2u,
+    /// PDF/A-3b (PDF/A-2b allowing arbitrary embedded files)
+    A3b,
+    /// PDF/A-3u (PDF/A-3b with Unicode-mapped text)
+    A3u,
+}
+
+impl std::fmt::Display for PdfStandard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PdfStandard::A1b => "PDF/A-1b",
+            PdfStandard::A2b => "PDF/A-2b",
+            PdfStandard::A2u => "PDF/A-2u",
+            PdfStandard::A3b => "PDF/A-3b",
+            PdfStandard::A3u => "PDF/A-3u",
+        };
+        f.write_str(name)
+    }
+}
+
 /// An artifact produced by rendering.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Artifact {
     /// The binary content of the artifact
     pub bytes: Vec<u8>,
     /// The format of the output
     pub output_format: OutputFormat,
+    /// 0-based index of the document page this artifact holds, for
+    /// per-page formats (SVG, PNG). `None` when the artifact holds the whole
+    /// document (PDF, TXT).
+    pub page: Option<usize>,
 }
 
 /// Internal rendering options.
+///
+/// Marked `#[non_exhaustive]` so new options are not breaking changes:
+/// outside this crate, build it with [`RenderOptions::builder`] or start
+/// from [`RenderOptions::default`] and assign fields.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct RenderOptions {
     /// Optional output format specification
     pub output_format: Option<OutputFormat>,
@@ -38,6 +75,27 @@ pub struct RenderOptions {
     /// Backends that do not support page selection (notably PDF) return
     /// a `FormatNotSupported` error when this is `Some`.
     pub pages: Option<Vec<usize>>,
+    /// Maximum number of pages the document may have. A document with more
+    /// pages fails with [`crate::RenderError::TooManyPages`]. `None` means no
+    /// limit.
+    pub max_pages: Option<usize>,
+    /// Skip the "document body is empty" warning for documents with no body
+    /// text, e.g. when the plate renders entirely from frontmatter fields.
+    pub allow_empty_body: bool,
+    /// Fail instead of falling back to a default font when the plate asks
+    /// for a font family the quill does not provide. Without this, the
+    /// fallback is reported as a warning.
+    pub strict_fonts: bool,
+    /// Card types to render. Cards of any other type are dropped before
+    /// data compilation; listed types absent from the document are ignored.
+    /// `None` renders every card.
+    pub include_cards: Option<Vec<String>>,
+    /// Seed passed to the plate as the `SEED` data field, for templates that
+    /// derive pseudo-random layout from it. `None` leaves it out.
+    pub seed: Option<u64>,
+    /// Archival standard the PDF output must conform to. `None` produces a
+    /// regular PDF. Ignored for other output formats.
+    pub pdf_standard: Option<PdfStandard>,
 }
 
 impl Default for RenderOptions {
@@ -46,6 +104,116 @@ impl Default for RenderOptions {
             output_format: None,
             ppi: None,
             pages: None,
+            max_pages: None,
+            allow_empty_body: false,
+            strict_fonts: false,
+            include_cards: None,
+            seed: None,
+            pdf_standard: None,
         }
     }
 }
+
+impl RenderOptions {
+    /// Start building options from the defaults.
+    pub fn builder() -> RenderOptionsBuilder {
+        RenderOptionsBuilder::default()
+    }
+}
+
+/// Builder for [`RenderOptions`], created by [`RenderOptions::builder`].
+/// Options left unset keep their [`Default`] values.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptionsBuilder {
+    options: RenderOptions,
+}
+
+impl RenderOptionsBuilder {
+    /// Set [`RenderOptions::output_format`].
+    pub fn output_format(mut self, format: OutputFormat) -> Self {
+        self.options.output_format = Some(format);
+        self
+    }
+
+    /// Set [`RenderOptions::ppi`].
+    pub fn ppi(mut self, ppi: f32) -> Self {
+        self.options.ppi = Some(ppi);
+        self
+    }
+
+    /// Set [`RenderOptions::pages`].
+    pub fn pages(mut self, pages: Vec<usize>) -> Self {
+        self.options.pages = Some(pages);
+        self
+    }
+
+    /// Set [`RenderOptions::max_pages`].
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.options.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Set [`RenderOptions::allow_empty_body`].
+    pub fn allow_empty_body(mut self, allow: bool) -> Self {
+        self.options.allow_empty_body = allow;
+        self
+    }
+
+    /// Set [`RenderOptions::strict_fonts`].
+    pub fn strict_fonts(mut self, strict: bool) -> Self {
+        self.options.strict_fonts = strict;
+        self
+    }
+
+    /// Set [`RenderOptions::include_cards`].
+    pub fn include_cards<I, S>(mut self, card_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.include_cards = Some(card_types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set [`RenderOptions::seed`].
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.options.seed = Some(seed);
+        self
+    }
+
+    /// Set [`RenderOptions::pdf_standard`].
+    pub fn pdf_standard(mut self, standard: PdfStandard) -> Self {
+        self.options.pdf_standard = Some(standard);
+        self
+    }
+
+    /// Build the options.
+    pub fn build(self) -> RenderOptions {
+        self.options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_sets_fields_and_keeps_defaults() {
+        let opts = RenderOptions::builder()
+            .output_format(OutputFormat::Png)
+            .ppi(300.0)
+            .include_cards(["note"])
+            .strict_fonts(true)
+            .build();
+        assert_eq!(opts.output_format, Some(OutputFormat::Png));
+        assert_eq!(opts.ppi, Some(300.0));
+        assert_eq!(opts.include_cards, Some(vec!["note".to_string()]));
+        assert!(opts.strict_fonts);
+
+        assert_eq!(opts.pages, None);
+        assert_eq!(opts.max_pages, None);
+        assert!(!opts.allow_empty_body);
+        assert_eq!(opts.seed, None);
+        assert_eq!(opts.pdf_standard, None);
+    }
+}
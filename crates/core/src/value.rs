@@ -38,6 +38,44 @@ impl QuillValue {
     }
 }
 
+/// Inferred JSON-level type of a [`QuillValue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    /// A string value
+    String,
+    /// An integer or floating-point number
+    Number,
+    /// A boolean value
+    Bool,
+    /// An array; carries the element type when every element shares one
+    /// (`None` for empty or mixed arrays)
+    Array(Option<Box<FieldType>>),
+    /// A mapping
+    Object,
+    /// An explicit null
+    Null,
+}
+
+impl FieldType {
+    /// Classify a JSON value.
+    pub fn of(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::String(_) => FieldType::String,
+            serde_json::Value::Number(_) => FieldType::Number,
+            serde_json::Value::Bool(_) => FieldType::Bool,
+            serde_json::Value::Object(_) => FieldType::Object,
+            serde_json::Value::Null => FieldType::Null,
+            serde_json::Value::Array(items) => {
+                let mut types = items.iter().map(FieldType::of);
+                let element = types
+                    .next()
+                    .and_then(|first| types.all(|t| t == first).then(|| Box::new(first)));
+                FieldType::Array(element)
+            }
+        }
+    }
+}
+
 impl Deref for QuillValue {
     type Target = serde_json::Value;
 
@@ -48,6 +86,11 @@ impl Deref for QuillValue {
 
 // Implement common delegating methods for convenience
 impl QuillValue {
+    /// The inferred type of this value
+    pub fn field_type(&self) -> FieldType {
+        FieldType::of(&self.0)
+    }
+
     /// Check if the value is null
     pub fn is_null(&self) -> bool {
         self.0.is_null()
@@ -92,6 +135,67 @@ impl QuillValue {
     pub fn get(&self, key: &str) -> Option<QuillValue> {
         self.0.get(key).map(|v| QuillValue(v.clone()))
     }
+
+    /// Get an element from an array by index
+    pub fn get_index(&self, index: usize) -> Option<QuillValue> {
+        self.0.get(index).map(|v| QuillValue(v.clone()))
+    }
+
+    /// Length of a collection-like value: characters in a string (not
+    /// bytes), elements in an array, keys in an object. `None` for numbers,
+    /// booleans, and null.
+    pub fn len(&self) -> Option<usize> {
+        match &self.0 {
+            serde_json::Value::String(s) => Some(s.chars().count()),
+            serde_json::Value::Array(items) => Some(items.len()),
+            serde_json::Value::Object(map) => Some(map.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether a string, array, or object is empty. `None` for values
+    /// without a [`QuillValue::len`].
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Nesting depth of the value: 0 for scalars, 1 for an array or object of
+    /// scalars, and so on.
+    ///
+    /// Computed without recursion, so it is safe to call on arbitrarily deep
+    /// values before handing them to recursive code. Recursive operations
+    /// should reject values deeper than [`crate::error::MAX_NESTING_DEPTH`].
+    /// (JSON values cannot contain cycles, so depth is the only hazard.)
+    pub fn nesting_depth(&self) -> usize {
+        let mut max = 0;
+        let mut stack = vec![(&self.0, 0usize)];
+        while let Some((value, depth)) = stack.pop() {
+            let children: Box<dyn Iterator<Item = &serde_json::Value>> = match value {
+                serde_json::Value::Array(items) => Box::new(items.iter()),
+                serde_json::Value::Object(map) => Box::new(map.values()),
+                _ => continue,
+            };
+            max = max.max(depth + 1);
+            stack.extend(children.map(|child| (child, depth + 1)));
+        }
+        max
+    }
+
+    /// Whether the value counts as "set" in a conditional.
+    ///
+    /// `null`, `false`, zero, `""`, `[]`, and `{}` are falsy; everything
+    /// else is truthy. Strings are not parsed, so `"false"` and `"0"` are
+    /// truthy.
+    pub fn is_truthy(&self) -> bool {
+        match &self.0 {
+            serde_json::Value::Null => false,
+            serde_json::Value::Bool(b) => *b,
+            serde_json::Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+            serde_json::Value::String(s) => !s.is_empty(),
+            serde_json::Value::Array(items) => !items.is_empty(),
+            serde_json::Value::Object(map) => !map.is_empty(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -220,4 +324,86 @@ mod tests {
             Some("2d lt example")
         );
     }
+
+    #[test]
+    fn test_len_and_index() {
+        use serde_json::json;
+
+        let string = QuillValue::from_json(json!("Crème brûlée"));
+        assert_eq!(string.len(), Some(12));
+        assert_eq!(string.is_empty(), Some(false));
+        assert_eq!(QuillValue::from_json(json!("")).is_empty(), Some(true));
+
+        let array = QuillValue::from_json(json!(["a", {"b": 1}, null]));
+        assert_eq!(array.len(), Some(3));
+        assert_eq!(
+            array.get_index(1).unwrap().get("b").unwrap().as_i64(),
+            Some(1)
+        );
+        assert_eq!(array.get_index(3), None);
+
+        let object = QuillValue::from_json(json!({"x": 1, "y": [1, 2]}));
+        assert_eq!(object.len(), Some(2));
+        assert_eq!(object.get_index(0), None);
+
+        for scalar in [json!(7), json!(true), json!(null)] {
+            let scalar = QuillValue::from_json(scalar);
+            assert_eq!(scalar.len(), None);
+            assert_eq!(scalar.is_empty(), None);
+        }
+    }
+
+    #[test]
+    fn test_is_truthy() {
+        use serde_json::json;
+
+        for falsy in [
+            json!(null),
+            json!(false),
+            json!(0),
+            json!(0.0),
+            json!(""),
+            json!([]),
+            json!({}),
+        ] {
+            assert!(
+                !QuillValue::from_json(falsy.clone()).is_truthy(),
+                "expected falsy: {}",
+                falsy
+            );
+        }
+        for truthy in [
+            json!(true),
+            json!(-1),
+            json!(0.5),
+            json!("false"),
+            json!(" "),
+            json!([null]),
+            json!({"a": null}),
+        ] {
+            assert!(
+                QuillValue::from_json(truthy.clone()).is_truthy(),
+                "expected truthy: {}",
+                truthy
+            );
+        }
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+        use serde_json::json;
+
+        assert_eq!(QuillValue::from_json(json!("x")).nesting_depth(), 0);
+        assert_eq!(QuillValue::from_json(json!([])).nesting_depth(), 1);
+        assert_eq!(
+            QuillValue::from_json(json!({"a": [1, {"b": []}], "c": 2})).nesting_depth(),
+            4
+        );
+
+        let mut deep = json!(null);
+        for _ in 0..1000 {
+            deep = json!([deep]);
+        }
+        assert_eq!(QuillValue::from_json(deep).nesting_depth(), 1000);
+    }
 }
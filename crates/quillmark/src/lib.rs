@@ -12,17 +12,17 @@
 //! let quill = engine.quill_from_path("path/to/quill").unwrap();
 //!
 //! let parsed = Document::from_markdown("---\nQUILL: my_quill\ntitle: Hello\n---\n# Hello World").unwrap();
-//! let result = quill.render(&parsed, &RenderOptions {
-//!     output_format: Some(OutputFormat::Pdf),
-//!     ..Default::default()
-//! }).unwrap();
+//! let result = quill
+//!     .render(&parsed, &RenderOptions::builder().output_format(OutputFormat::Pdf).build())
+//!     .unwrap();
 //! ```
 
 // Re-export core types for convenience. Note: `QuillSource` is not re-exported
 // at the crate root — Quillmark consumers work with the renderable `Quill`.
 pub use quillmark_core::{
-    Artifact, Backend, Card, Diagnostic, Document, Location, OutputFormat, ParseError, ParseOutput,
-    RenderError, RenderOptions, RenderResult, RenderSession, Severity,
+    Artifact, Backend, BodySpans, Card, CardDiff, Diagnostic, Document, DumpFormat, Location,
+    OutputFormat, ParseError, ParseOptions, ParseOutput, PdfStandard, QuillIgnore, RenderError,
+    RenderOptions, RenderOptionsBuilder, RenderResult, RenderSession, Severity,
 };
 
 // Declare modules
@@ -33,4 +33,6 @@ pub mod orchestration;
 pub use form::{Form, FormCard, FormFieldSource, FormFieldValue};
 
 // Re-export types from orchestration module
-pub use orchestration::{Quill, Quillmark};
+pub use orchestration::{
+    Quill, Quillmark, QuillmarkBuilder, RenderCacheStats, RenderPlan, RenderTimings,
+};
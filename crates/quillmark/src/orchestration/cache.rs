@@ -0,0 +1,121 @@
+//! In-process LRU cache of rendered artifacts.
+//!
+//! Keyed by the quill's content fingerprint, the backend, the document's
+//! plate JSON, and the render options, so a hit is only possible when every
+//! input to compilation is identical. Editing, adding, or removing any quill
+//! file changes the fingerprint and therefore misses.
+
+use std::sync::Mutex;
+
+use indexmap::IndexMap;
+use quillmark_core::{Document, OutputFormat, PdfStandard, RenderOptions, RenderResult};
+
+/// Hit/miss counters and occupancy of an engine's render cache, from
+/// [`crate::Quillmark::render_cache_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderCacheStats {
+    /// Renders served from the cache
+    pub hits: u64,
+    /// Renders that compiled and were then cached
+    pub misses: u64,
+    /// Results currently cached
+    pub entries: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    fingerprint: String,
+    backend: String,
+    document: String,
+    output_format: Option<OutputFormat>,
+    ppi_bits: Option<u32>,
+    pages: Option<Vec<usize>>,
+    max_pages: Option<usize>,
+    allow_empty_body: bool,
+    strict_fonts: bool,
+    include_cards: Option<Vec<String>>,
+    seed: Option<u64>,
+    pdf_standard: Option<PdfStandard>,
+}
+
+impl CacheKey {
+    pub(crate) fn new(
+        fingerprint: &str,
+        backend: &str,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Self {
+        Self {
+            fingerprint: fingerprint.to_string(),
+            backend: backend.to_string(),
+            document: doc.to_plate_json().to_string(),
+            output_format: opts.output_format,
+            ppi_bits: opts.ppi.map(f32::to_bits),
+            pages: opts.pages.clone(),
+            max_pages: opts.max_pages,
+            allow_empty_body: opts.allow_empty_body,
+            strict_fonts: opts.strict_fonts,
+            include_cards: opts.include_cards.clone(),
+            seed: opts.seed,
+            pdf_standard: opts.pdf_standard,
+        }
+    }
+}
+
+struct CacheState {
+    entries: IndexMap<CacheKey, RenderResult>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Least-recently-used map from [`CacheKey`] to render results, shared by
+/// every quill an engine assembles.
+pub(crate) struct RenderCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl RenderCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState {
+                entries: IndexMap::new(),
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    /// Cached result for `key`, marking it most recently used.
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<RenderResult> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let index = state.entries.get_index_of(key)?;
+        let last = state.entries.len() - 1;
+        state.entries.move_index(index, last);
+        state.hits += 1;
+        state
+            .entries
+            .get_index(last)
+            .map(|(_, result)| result.clone())
+    }
+
+    /// Cache `result`, evicting the least recently used entry when full.
+    pub(crate) fn insert(&self, key: CacheKey, result: &RenderResult) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.misses += 1;
+        if state.entries.len() >= self.capacity {
+            state.entries.shift_remove_index(0);
+        }
+        state.entries.insert(key, result.clone());
+    }
+
+    pub(crate) fn stats(&self) -> RenderCacheStats {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        RenderCacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+        }
+    }
+}
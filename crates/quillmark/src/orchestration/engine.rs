@@ -1,31 +1,38 @@
 use quillmark_core::{
-    Backend, Diagnostic, FileTreeNode, QuillIgnore, QuillSource, RenderError, Severity,
+    error::{QuillError, MAX_EXPANSION_DEPTH},
+    quill, Backend, Diagnostic, Document, FileTreeNode, QuillIgnore, QuillSource, RenderError,
+    RenderOptions, RenderResult, Severity,
 };
 use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use super::cache::{RenderCache, RenderCacheStats};
 use super::Quill;
 
 /// High-level engine for orchestrating backends and quills.
 pub struct Quillmark {
     backends: HashMap<String, Arc<dyn Backend>>,
+    render_cache: Option<Arc<RenderCache>>,
 }
 
 impl Quillmark {
     /// Create a new Quillmark with auto-registered backends based on enabled features.
     pub fn new() -> Self {
-        let mut engine = Self {
-            backends: HashMap::new(),
-        };
+        Self::builder().build()
+    }
 
-        #[cfg(feature = "typst")]
-        {
-            engine.register_backend(Box::new(quillmark_typst::TypstBackend));
+    /// Start building an engine with an explicit backend set.
+    ///
+    /// The builder registers the feature-enabled default backends unless
+    /// [`QuillmarkBuilder::without_default_backends`] is called.
+    pub fn builder() -> QuillmarkBuilder {
+        QuillmarkBuilder {
+            default_backends: true,
+            backends: Vec::new(),
+            render_cache_capacity: 0,
         }
-
-        engine
     }
 
     /// Register a backend with the engine.
@@ -36,29 +43,60 @@ impl Quillmark {
 
     /// Build and return a render-ready quill from an in-memory file tree.
     pub fn quill(&self, tree: FileTreeNode) -> Result<Quill, RenderError> {
-        let source = QuillSource::from_tree(tree).map_err(|e| RenderError::QuillConfig {
-            diag: Box::new(
-                Diagnostic::new(
-                    Severity::Error,
-                    format!("Failed to load quill from tree: {}", e),
-                )
-                .with_code("quill::load_failed".to_string()),
-            ),
+        let source = QuillSource::from_tree(tree).map_err(|e| {
+            let mut diag = e.to_diagnostic();
+            diag.message = format!("Failed to load quill from tree: {}", diag.message);
+            RenderError::QuillConfig {
+                diag: Box::new(diag),
+            }
         })?;
         self.assemble(source)
     }
 
     /// Load a quill from a filesystem path and attach the appropriate backend.
+    ///
+    /// Files matched by the directory's `.quillignore` are skipped; without
+    /// one, [`QuillIgnore::defaults`] applies.
+    ///
+    /// A quill whose Quill.yaml sets `quill.extends: <path>` (relative to the
+    /// quill directory) is laid over that base quill: its files win on path
+    /// collisions and its Quill.yaml values override the base's. Bases may
+    /// extend further bases; a chain that loops back fails with
+    /// `quill::extends_cycle`, and one deeper than
+    /// [`quillmark_core::error::MAX_EXPANSION_DEPTH`] with
+    /// `quill::extends_too_deep`.
     pub fn quill_from_path<P: AsRef<Path>>(&self, path: P) -> Result<Quill, RenderError> {
-        let tree = load_tree_from_path(path.as_ref()).map_err(|e| RenderError::QuillConfig {
-            diag: Box::new(
-                Diagnostic::new(Severity::Error, format!("Failed to load quill: {}", e))
-                    .with_code("quill::load_failed".to_string()),
-            ),
-        })?;
+        let path = path.as_ref();
+        let ignore = read_quillignore(path).map_err(load_failed)?;
+        self.quill_from_path_with_ignore(path, ignore)
+    }
+
+    /// Like [`Quillmark::quill_from_path`], skipping the files matched by
+    /// `ignore` instead of the directory's `.quillignore` or the defaults.
+    /// Start from [`QuillIgnore::defaults`] to supplement them rather than
+    /// replace them.
+    pub fn quill_from_path_with_ignore<P: AsRef<Path>>(
+        &self,
+        path: P,
+        ignore: QuillIgnore,
+    ) -> Result<Quill, RenderError> {
+        let tree = load_inherited(path.as_ref(), &ignore, &mut Vec::new())?;
         self.quill(tree)
     }
 
+    /// Load the quill at `path` and render `doc` with it in one call.
+    ///
+    /// Sugar over [`Quillmark::quill_from_path`] followed by [`Quill::render`];
+    /// load the quill once and reuse it when rendering many documents.
+    pub fn render<P: AsRef<Path>>(
+        &self,
+        path: P,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Result<RenderResult, RenderError> {
+        self.quill_from_path(path)?.render(doc, opts)
+    }
+
     fn assemble(&self, source: QuillSource) -> Result<Quill, RenderError> {
         let backend_id = source.backend_id();
         let backend =
@@ -77,13 +115,21 @@ impl Quillmark {
                         )),
                     ),
                 })?;
-        Ok(Quill::new(Arc::new(source), Arc::clone(backend)))
+        backend.validate_quill(&source)?;
+        Ok(Quill::new(Arc::new(source), Arc::clone(backend))
+            .with_render_cache(self.render_cache.clone()))
     }
 
     /// Get a list of registered backend IDs.
     pub fn registered_backends(&self) -> Vec<&str> {
         self.backends.keys().map(|s| s.as_str()).collect()
     }
+
+    /// Hit/miss counters of the render cache, or `None` when the engine was
+    /// built without one (see [`QuillmarkBuilder::render_cache`]).
+    pub fn render_cache_stats(&self) -> Option<RenderCacheStats> {
+        self.render_cache.as_ref().map(|cache| cache.stats())
+    }
 }
 
 impl Default for Quillmark {
@@ -92,29 +138,125 @@ impl Default for Quillmark {
     }
 }
 
-/// Walk a filesystem path into an in-memory [`FileTreeNode`].
-///
-/// Honours a `.quillignore` file at the root; otherwise applies a default
-/// ignore set (`.git/`, `target/`, `node_modules/`, etc.).
-fn load_tree_from_path(path: &Path) -> Result<FileTreeNode, Box<dyn StdError + Send + Sync>> {
-    use std::fs;
+/// Builder for a [`Quillmark`] engine, created by [`Quillmark::builder`].
+pub struct QuillmarkBuilder {
+    default_backends: bool,
+    backends: Vec<Box<dyn Backend>>,
+    render_cache_capacity: usize,
+}
+
+impl QuillmarkBuilder {
+    /// Skip the feature-enabled backends that [`Quillmark::new`] registers.
+    pub fn without_default_backends(mut self) -> Self {
+        self.default_backends = false;
+        self
+    }
+
+    /// Register `backend`, replacing any default backend with the same id.
+    pub fn with_backend(mut self, backend: Box<dyn Backend>) -> Self {
+        self.backends.push(backend);
+        self
+    }
 
+    /// Cache up to `capacity` render results in process, least recently used
+    /// evicted first. [`Quill::render`] on any quill from this engine then
+    /// returns the cached artifacts for a repeated render of the same quill
+    /// contents, document, and options without compiling. `0` (the default)
+    /// disables caching.
+    pub fn render_cache(mut self, capacity: usize) -> Self {
+        self.render_cache_capacity = capacity;
+        self
+    }
+
+    /// Build the engine.
+    pub fn build(self) -> Quillmark {
+        let mut engine = Quillmark {
+            backends: HashMap::new(),
+            render_cache: (self.render_cache_capacity > 0)
+                .then(|| Arc::new(RenderCache::new(self.render_cache_capacity))),
+        };
+
+        #[cfg(feature = "typst")]
+        if self.default_backends {
+            engine.register_backend(Box::new(quillmark_typst::TypstBackend));
+        }
+
+        for backend in self.backends {
+            engine.register_backend(backend);
+        }
+        engine
+    }
+}
+
+/// Load the quill at `path` and, if it extends a base quill, lay it over
+/// that base. `chain` holds the canonical paths of the quills currently
+/// being loaded, to detect cycles.
+fn load_inherited(
+    path: &Path,
+    ignore: &QuillIgnore,
+    chain: &mut Vec<PathBuf>,
+) -> Result<FileTreeNode, RenderError> {
+    let quill_error = |e: QuillError| RenderError::QuillConfig {
+        diag: Box::new(e.to_diagnostic()),
+    };
+
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| load_failed(format!("{}: {}", path.display(), e).into()))?;
+    if chain.contains(&canonical) {
+        let chain = chain
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(quill_error(QuillError::ExtendsCycle { chain }));
+    }
+    if chain.len() >= MAX_EXPANSION_DEPTH {
+        let chain = chain
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(quill_error(QuillError::ExtendsTooDeep {
+            chain,
+            max: MAX_EXPANSION_DEPTH,
+        }));
+    }
+
+    let tree = load_dir(path, path, ignore).map_err(load_failed)?;
+    let Some(base) = quill::extends_target(&tree).map_err(quill_error)? else {
+        return Ok(tree);
+    };
+
+    chain.push(canonical);
+    let base_path = path.join(base);
+    let base_ignore = read_quillignore(&base_path).map_err(load_failed)?;
+    let base_tree = load_inherited(&base_path, &base_ignore, chain)?;
+    chain.pop();
+
+    quill::inherit(base_tree, tree).map_err(quill_error)
+}
+
+fn load_failed(e: Box<dyn StdError + Send + Sync>) -> RenderError {
+    RenderError::QuillConfig {
+        diag: Box::new(
+            Diagnostic::new(Severity::Error, format!("Failed to load quill: {}", e))
+                .with_code("quill::load_failed".to_string()),
+        ),
+    }
+}
+
+/// The ignore set for the quill directory at `path`: its `.quillignore`
+/// file when present, otherwise [`QuillIgnore::defaults`].
+fn read_quillignore(path: &Path) -> Result<QuillIgnore, Box<dyn StdError + Send + Sync>> {
     let quillignore_path = path.join(".quillignore");
-    let ignore = if quillignore_path.exists() {
-        let content = fs::read_to_string(&quillignore_path)
+    if quillignore_path.exists() {
+        let content = std::fs::read_to_string(&quillignore_path)
             .map_err(|e| format!("Failed to read .quillignore: {}", e))?;
-        QuillIgnore::from_content(&content)
+        Ok(QuillIgnore::from_content(&content))
     } else {
-        QuillIgnore::new(vec![
-            ".git/".to_string(),
-            ".gitignore".to_string(),
-            ".quillignore".to_string(),
-            "target/".to_string(),
-            "node_modules/".to_string(),
-        ])
-    };
-
-    load_dir(path, path, &ignore)
+        Ok(QuillIgnore::defaults())
+    }
 }
 
 fn load_dir(
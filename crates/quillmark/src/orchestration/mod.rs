@@ -8,8 +8,12 @@
 //! 2. Load a quill with [`Quillmark::quill`] or [`Quillmark::quill_from_path`]
 //! 3. Render documents directly via [`Quill::render`] or [`Quill::open`]
 
+mod cache;
 mod engine;
+mod plan;
 mod quill;
 
-pub use engine::Quillmark;
-pub use quill::Quill;
+pub use cache::RenderCacheStats;
+pub use engine::{Quillmark, QuillmarkBuilder};
+pub use plan::RenderPlan;
+pub use quill::{Quill, RenderTimings};
@@ -0,0 +1,29 @@
+//! Render plans: what a render would do, without compiling.
+
+use quillmark_core::Diagnostic;
+
+/// Snapshot of how a [`crate::Quill`] would render a document, from
+/// [`crate::Quill::explain`]. Suitable for attaching to bug reports.
+#[derive(Debug, Clone)]
+pub struct RenderPlan {
+    /// The quill's declared name
+    pub quill: String,
+    /// Backend that would compile the document (e.g. `"typst"`)
+    pub backend: String,
+    /// Content fingerprint of the quill's file tree
+    pub fingerprint: String,
+    /// Plate file declared in Quill.yaml, if any
+    pub plate_file: Option<String>,
+    /// Length in bytes of the plate that would be compiled
+    pub plate_len: usize,
+    /// Quill-relative paths of bundled assets, fonts excluded
+    pub assets: Vec<String>,
+    /// Quill-relative paths of bundled font files
+    pub fonts: Vec<String>,
+    /// Files the plate loads, as reported by the backend
+    pub referenced_assets: Vec<String>,
+    /// Parse warnings, plus everything the render would report before
+    /// compilation (validation errors, quill reference mismatch, referenced
+    /// assets missing from the quill)
+    pub diagnostics: Vec<Diagnostic>,
+}
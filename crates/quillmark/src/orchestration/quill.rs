@@ -2,14 +2,20 @@
 //! [`QuillSource`] with a resolved backend.
 
 use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Write;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use quillmark_core::{
-    normalize::normalize_document, Backend, Card, Diagnostic, Document, Frontmatter, OutputFormat,
-    QuillSource, QuillValue, RenderError, RenderOptions, RenderResult, RenderSession, Sentinel,
-    Severity,
+    document::interpolate_fields, normalize::normalize_document, Backend, Card, Diagnostic,
+    Document, Frontmatter, OutputFormat, ParseOptions, ParseOutput, QuillReference, QuillSource,
+    QuillValue, RenderError, RenderOptions, RenderResult, RenderSession, Sentinel, Severity,
 };
 
+use super::cache::{CacheKey, RenderCache};
+use super::plan::RenderPlan;
 use crate::form::{self, Form, FormCard};
 
 /// Renderable quill. Composes an [`Arc<QuillSource>`] with a resolved
@@ -18,20 +24,84 @@ use crate::form::{self, Form, FormCard};
 pub struct Quill {
     source: Arc<QuillSource>,
     backend: Arc<dyn Backend>,
+    render_cache: Option<Arc<RenderCache>>,
+}
+
+/// Wall-clock time spent in each stage of [`Quill::render_timed`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderTimings {
+    /// Coercion, validation, normalization, and defaults (plate data).
+    pub data: Duration,
+    /// Backend compilation of the plate.
+    pub compile: Duration,
+    /// Encoding the compiled pages into artifacts.
+    pub output: Duration,
+    /// Pages in the compiled document.
+    pub page_count: usize,
 }
 
 struct PreparedRenderContext {
     json_data: serde_json::Value,
     plate_content: String,
+    /// The only format the session will render, if known, so the backend
+    /// can skip assets for other formats.
+    output_format: Option<OutputFormat>,
 }
 
 impl Quill {
     /// Construct a Quill from a source and a resolved backend.
     ///
     /// Engine-internal; external callers should use
-    /// [`crate::Quillmark::quill`] or [`crate::Quillmark::quill_from_path`].
+    /// [`crate::Quillmark::quill`], [`crate::Quillmark::quill_from_path`],
+    /// or [`Quill::from_parts`].
     pub(crate) fn new(source: Arc<QuillSource>, backend: Arc<dyn Backend>) -> Self {
-        Self { source, backend }
+        Self {
+            source,
+            backend,
+            render_cache: None,
+        }
+    }
+
+    pub(crate) fn with_render_cache(mut self, cache: Option<Arc<RenderCache>>) -> Self {
+        self.render_cache = cache;
+        self
+    }
+
+    /// Rebuild a quill from a source and backend, typically the parts of
+    /// another quill (see [`Quill::into_parts`]) with a modified source.
+    ///
+    /// The backend must be the one the source names and must accept the
+    /// source (see [`Backend::validate_quill`]). The result has no render
+    /// cache.
+    pub fn from_parts(
+        source: Arc<QuillSource>,
+        backend: Arc<dyn Backend>,
+    ) -> Result<Self, RenderError> {
+        if source.backend_id() != backend.id() {
+            return Err(RenderError::UnsupportedBackend {
+                diag: Box::new(
+                    Diagnostic::new(
+                        Severity::Error,
+                        format!(
+                            "Quill '{}' needs backend '{}', got '{}'",
+                            source.name(),
+                            source.backend_id(),
+                            backend.id()
+                        ),
+                    )
+                    .with_code("quill::backend_mismatch".to_string()),
+                ),
+            });
+        }
+        backend.validate_quill(&source)?;
+        Ok(Self::new(source, backend))
+    }
+
+    /// Split the quill into its source and backend, e.g. to pair the
+    /// backend with a different source via [`Quill::from_parts`] without
+    /// going back through the engine.
+    pub fn into_parts(self) -> (Arc<QuillSource>, Arc<dyn Backend>) {
+        (self.source, self.backend)
     }
 
     /// The underlying quill source.
@@ -49,44 +119,563 @@ impl Quill {
         self.backend.supported_formats()
     }
 
+    /// Output formats this quill renders: the backend's formats, narrowed
+    /// to `quill.formats` when the quill declares it.
+    pub fn output_formats(&self) -> Vec<OutputFormat> {
+        let backend_formats = self.backend.supported_formats().iter().copied();
+        match &self.source.config().formats {
+            Some(declared) => backend_formats.filter(|f| declared.contains(f)).collect(),
+            None => backend_formats.collect(),
+        }
+    }
+
     /// The quill's declared name.
     pub fn name(&self) -> &str {
         self.source.name()
     }
 
+    /// Parse Markdown destined for this quill.
+    ///
+    /// When the quill declares `card_types`, parsing is strict: a `CARD:` tag
+    /// the quill does not declare fails with `parse::unknown_card` instead of
+    /// producing a card the plate never reads. Quills without `card_types`
+    /// parse leniently, like [`Document::from_markdown_with_warnings`].
+    pub fn parse(&self, markdown: &str) -> Result<ParseOutput, RenderError> {
+        let card_types = &self.source.config().card_types;
+        let options = if card_types.is_empty() {
+            ParseOptions::default()
+        } else {
+            ParseOptions::strict(card_types.iter().map(|card| card.name.clone()))
+        };
+        Document::from_markdown_with_options(markdown, &options).map_err(|e| {
+            RenderError::InvalidFrontmatter {
+                diag: Box::new(e.to_diagnostic()),
+            }
+        })
+    }
+
     /// Render a document to final artifacts.
     ///
     /// Pass `&RenderOptions::default()` for backend defaults (first supported
     /// format, backend-chosen ppi, all pages).
+    ///
+    /// When the engine was built with a render cache, a repeated render of
+    /// the same document and options returns the cached result.
     pub fn render(
         &self,
         doc: &Document,
         opts: &RenderOptions,
     ) -> Result<RenderResult, RenderError> {
-        let session = self.open(doc)?;
-        let resolved = self.resolve_options(opts);
-        session.render(&resolved)
+        let resolved = self.resolve_options(opts)?;
+        let selected = select_cards(doc, &resolved);
+        let doc = selected.as_ref();
+        let cache_key = self
+            .render_cache
+            .as_ref()
+            .map(|_| CacheKey::new(self.source.fingerprint(), self.backend_id(), doc, &resolved));
+        if let (Some(cache), Some(key)) = (&self.render_cache, &cache_key) {
+            if let Some(hit) = cache.get(key) {
+                return Ok(hit);
+            }
+        }
+
+        let context = self.prepare_render_context(doc, &resolved)?;
+        let session = self.open_prepared(doc, &context)?;
+        let mut result = session.render(&resolved)?;
+        self.push_empty_body_warning(doc, opts, &mut result);
+
+        if let (Some(cache), Some(key)) = (&self.render_cache, cache_key) {
+            cache.insert(key, &result);
+        }
+        Ok(result)
+    }
+
+    /// Render a document like [`Quill::render`] and write its artifact into
+    /// `writer`, returning the render's warnings.
+    ///
+    /// Meant for single-artifact formats such as PDF; formats that produce
+    /// one artifact per page (SVG, PNG) fail with
+    /// `quill::multiple_artifacts`. A failed write fails with
+    /// [`RenderError::OutputFailed`].
+    pub fn render_to_writer(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+        writer: &mut impl Write,
+    ) -> Result<Vec<Diagnostic>, RenderError> {
+        let result = self.render(doc, opts)?;
+        if result.artifacts.len() > 1 {
+            return Err(RenderError::FormatNotSupported {
+                diag: Box::new(
+                    Diagnostic::new(
+                        Severity::Error,
+                        format!(
+                            "{:?} output has {} artifacts; render_to_writer writes exactly one",
+                            result.output_format,
+                            result.artifacts.len()
+                        ),
+                    )
+                    .with_code("quill::multiple_artifacts".to_string())
+                    .with_hint("Use Quill::render for per-page formats".to_string()),
+                ),
+            });
+        }
+        for artifact in &result.artifacts {
+            writer
+                .write_all(&artifact.bytes)
+                .map_err(|e| output_failed(&e))?;
+        }
+        writer.flush().map_err(|e| output_failed(&e))?;
+        Ok(result.warnings)
+    }
+
+    /// Render a document once into each of `formats`, compiling it a single
+    /// time and encoding the compiled pages per format.
+    ///
+    /// `opts` applies to every format; its `output_format` is ignored. Fails
+    /// before compiling if any format is unsupported. Bypasses the render
+    /// cache.
+    pub fn render_all_formats(
+        &self,
+        doc: &Document,
+        formats: &[OutputFormat],
+        opts: &RenderOptions,
+    ) -> Result<HashMap<OutputFormat, RenderResult>, RenderError> {
+        let resolved = formats
+            .iter()
+            .map(|format| {
+                let mut format_opts = opts.clone();
+                format_opts.output_format = Some(*format);
+                self.resolve_options(&format_opts)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(first) = resolved.first() else {
+            return Ok(HashMap::new());
+        };
+
+        let selected = select_cards(doc, first);
+        let doc = selected.as_ref();
+        let mut context = self.prepare_render_context(doc, first)?;
+        // One compile serves every format, so it needs every asset.
+        context.output_format = None;
+        let session = self.open_prepared(doc, &context)?;
+        let mut results = HashMap::with_capacity(resolved.len());
+        for format_opts in &resolved {
+            let mut result = session.render(format_opts)?;
+            self.push_empty_body_warning(doc, opts, &mut result);
+            results.insert(result.output_format, result);
+        }
+        Ok(results)
+    }
+
+    /// Render one output per `card_type` card in `doc`, e.g. one letter per
+    /// `recipient` card for a mail merge.
+    ///
+    /// Each render sees the main card, the cards of other types, and only
+    /// its own `card_type` card, in document order. Results follow the order
+    /// of the cards; a document without `card_type` cards gives an empty
+    /// `Vec`. Bypasses the render cache.
+    pub fn render_each(
+        &self,
+        doc: &Document,
+        card_type: &str,
+        opts: &RenderOptions,
+    ) -> Result<Vec<RenderResult>, RenderError> {
+        let resolved = self.resolve_options(opts)?;
+        let selected = select_cards(doc, &resolved);
+        let doc = selected.as_ref();
+        let mut results = Vec::new();
+        for (index, _) in doc
+            .cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.tag() == card_type)
+        {
+            let cards = doc
+                .cards()
+                .iter()
+                .enumerate()
+                .filter(|(i, card)| *i == index || card.tag() != card_type)
+                .map(|(_, card)| card.clone())
+                .collect();
+            let item =
+                Document::from_main_and_cards(doc.main().clone(), cards, doc.warnings().to_vec());
+            let context = self.prepare_render_context(&item, &resolved)?;
+            let session = self.open_prepared(&item, &context)?;
+            let mut result = session.render(&resolved)?;
+            self.push_empty_body_warning(&item, opts, &mut result);
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    /// Re-render `new` given `previous`, an earlier version of the document,
+    /// and `previous_result`, its render with the same `opts`.
+    ///
+    /// Returns `previous_result` unchanged when [`Document::diff_cards`]
+    /// finds no difference. No backend recompiles individual cards yet, so
+    /// any change falls back to a full [`Quill::render`].
+    pub fn render_incremental(
+        &self,
+        previous: &Document,
+        new: &Document,
+        previous_result: &RenderResult,
+        opts: &RenderOptions,
+    ) -> Result<RenderResult, RenderError> {
+        let resolved = self.resolve_options(opts)?;
+        if previous.diff_cards(new).is_empty()
+            && resolved.output_format == Some(previous_result.output_format)
+        {
+            return Ok(previous_result.clone());
+        }
+        self.render(new, opts)
+    }
+
+    /// Render a document and return the plate JSON data it was compiled
+    /// from, computing that data once.
+    ///
+    /// The data is what [`Quill::compile_data`] returns for `doc`; use this
+    /// instead of calling both when debugging a render.
+    pub fn render_with_data(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Result<(RenderResult, serde_json::Value), RenderError> {
+        let resolved = self.resolve_options(opts)?;
+        let selected = select_cards(doc, &resolved);
+        let doc = selected.as_ref();
+        let context = self.prepare_render_context(doc, &resolved)?;
+        let session = self.open_prepared(doc, &context)?;
+        let mut result = session.render(&resolved)?;
+        self.push_empty_body_warning(doc, opts, &mut result);
+        Ok((result, context.json_data))
+    }
+
+    /// Render a document like [`Quill::render`], reporting how long each
+    /// stage took. Bypasses the render cache.
+    ///
+    /// [`Quill::render`] itself is not instrumented; use this when
+    /// debugging render performance.
+    pub fn render_timed(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Result<(RenderResult, RenderTimings), RenderError> {
+        let resolved = self.resolve_options(opts)?;
+        let selected = select_cards(doc, &resolved);
+        let doc = selected.as_ref();
+
+        let started = Instant::now();
+        let context = self.prepare_render_context(doc, &resolved)?;
+        let data = started.elapsed();
+
+        let started = Instant::now();
+        let session = self.open_prepared(doc, &context)?;
+        let compile = started.elapsed();
+
+        let started = Instant::now();
+        let mut result = session.render(&resolved)?;
+        let output = started.elapsed();
+
+        self.push_empty_body_warning(doc, opts, &mut result);
+        let timings = RenderTimings {
+            data,
+            compile,
+            output,
+            page_count: session.page_count(),
+        };
+        Ok((result, timings))
+    }
+
+    /// Render a document with the quill's fragment plate
+    /// (`quill.fragment_plate_file`) instead of its main plate, e.g. to embed
+    /// a section as an SVG snippet without the surrounding page layout.
+    ///
+    /// Data is compiled exactly as for [`Quill::render`]. Fails with
+    /// `quill::no_fragment_plate` when the quill declares no fragment plate.
+    pub fn render_fragment(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Result<RenderResult, RenderError> {
+        let plate_content = self
+            .source
+            .fragment_plate()
+            .ok_or_else(|| RenderError::QuillConfig {
+                diag: Box::new(
+                    Diagnostic::new(
+                        Severity::Error,
+                        format!("Quill '{}' declares no fragment plate", self.name()),
+                    )
+                    .with_code("quill::no_fragment_plate".to_string())
+                    .with_hint(
+                        "Add `fragment_plate_file` to the `quill:` section of Quill.yaml"
+                            .to_string(),
+                    ),
+                ),
+            })?
+            .to_string();
+        let resolved = self.resolve_options(opts)?;
+        let selected = select_cards(doc, &resolved);
+        let doc = selected.as_ref();
+        let context = PreparedRenderContext {
+            json_data: self.render_data(doc, &resolved)?,
+            plate_content,
+            output_format: resolved.output_format,
+        };
+        let session = self.open_prepared(doc, &context)?;
+        let mut result = session.render(&resolved)?;
+        self.push_empty_body_warning(doc, opts, &mut result);
+        Ok(result)
+    }
+
+    /// Render `doc` with this quill, preceded by the pages `overlay` renders
+    /// from `overlay_doc` (e.g. a cover page or letterhead quill).
+    ///
+    /// Pass `None` for `overlay_doc` to render the overlay with an empty
+    /// document (a static cover). Both quills must share a backend and
+    /// support the requested output format.
+    pub fn render_with_overlay(
+        &self,
+        doc: &Document,
+        overlay: &Quill,
+        overlay_doc: Option<&Document>,
+        opts: &RenderOptions,
+    ) -> Result<RenderResult, RenderError> {
+        let resolved = self.resolve_options(opts)?;
+        if let Some(format) = resolved.output_format {
+            if !overlay.output_formats().contains(&format) {
+                return Err(RenderError::FormatNotSupported {
+                    diag: Box::new(
+                        Diagnostic::new(
+                            Severity::Error,
+                            format!(
+                                "Overlay quill '{}' does not support {:?} output",
+                                overlay.name(),
+                                format
+                            ),
+                        )
+                        .with_code("quill::overlay_format_mismatch".to_string())
+                        .with_hint(format!("Supported formats: {:?}", overlay.output_formats())),
+                    ),
+                });
+            }
+        }
+
+        let blank;
+        let overlay_doc = match overlay_doc {
+            Some(d) => d,
+            None => {
+                blank = overlay.empty_document();
+                &blank
+            }
+        };
+        let overlay_context = overlay.prepare_render_context(overlay_doc, &resolved)?;
+        let mut session = overlay.open_prepared(overlay_doc, &overlay_context)?;
+        let context = self.prepare_render_context(doc, &resolved)?;
+        session.append(self.open_prepared(doc, &context)?)?;
+        let mut result = session.render(&resolved)?;
+        self.push_empty_body_warning(doc, opts, &mut result);
+        Ok(result)
+    }
+
+    /// A copy of `doc` whose main body is the quill's body template
+    /// (`quill.body_template_file`) with `{{ field }}` placeholders filled
+    /// from the document's fields and schema defaults.
+    ///
+    /// Rendering already does this for documents without a body; call this
+    /// to replace a body that is present. Returns `doc` unchanged when the
+    /// quill declares no body template. Fails with
+    /// `quill::body_template_too_large` when the filled body would exceed
+    /// [`quillmark_core::error::MAX_INTERPOLATED_SIZE`].
+    pub fn with_body_template(&self, doc: &Document) -> Result<Document, RenderError> {
+        let mut filled = doc.clone();
+        if let Some(template) = self.source.body_template() {
+            let fields = self.apply_frontmatter_defaults(&doc.main().frontmatter().to_index_map());
+            let body = interpolate_fields(template, &fields).map_err(|e| {
+                RenderError::ValidationFailed {
+                    diag: Box::new(
+                        Diagnostic::new(Severity::Error, format!("Body template: {}", e))
+                            .with_code("quill::body_template_too_large".to_string())
+                            .with_hint("Shorten the fields the body template repeats".to_string()),
+                    ),
+                }
+            })?;
+            filled.main_mut().replace_body(body);
+        }
+        Ok(filled)
+    }
+
+    /// A document with no fields, cards, or body that references this quill.
+    fn empty_document(&self) -> Document {
+        let main = Card::new_with_sentinel(
+            Sentinel::Main(QuillReference::latest(self.name().to_string())),
+            Frontmatter::new(),
+            String::new(),
+        );
+        Document::from_main_and_cards(main, Vec::new(), Vec::new())
+    }
+
+    /// Quill-relative paths of the files this quill's plate loads, as
+    /// reported by the backend.
+    ///
+    /// Diff against [`QuillSource::file_exists`] to report missing assets
+    /// before compiling.
+    pub fn referenced_assets(&self) -> Vec<String> {
+        self.plate_content()
+            .map(|plate| self.backend.referenced_assets(&plate))
+            .unwrap_or_default()
+    }
+
+    /// Describe how `doc` would be rendered without compiling it: backend,
+    /// plate, bundled assets and fonts, and the diagnostics a render would
+    /// report before compilation.
+    ///
+    /// Compiles the plate data (cheap) but never calls the backend.
+    pub fn explain(&self, doc: &Document) -> RenderPlan {
+        let font_prefix = format!("{}/fonts/", self.source.asset_dir());
+        let (fonts, assets): (Vec<String>, Vec<String>) = self
+            .source
+            .find_files(format!("{}/*", self.source.asset_dir()))
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .partition(|path| path.starts_with(&font_prefix));
+        let referenced_assets = self.referenced_assets();
+
+        let mut diagnostics = doc.warnings().to_vec();
+        if let Err(e) = self.compile_data(doc) {
+            diagnostics.extend(e.diagnostics().into_iter().cloned());
+        }
+        diagnostics.extend(self.ref_mismatch_warning(doc));
+        for asset in &referenced_assets {
+            if !self.source.file_exists(asset) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        format!("Plate references '{}', which is not in the quill", asset),
+                    )
+                    .with_code("quill::missing_referenced_asset".to_string()),
+                );
+            }
+        }
+
+        RenderPlan {
+            quill: self.name().to_string(),
+            backend: self.backend_id().to_string(),
+            fingerprint: self.source.fingerprint().to_string(),
+            plate_file: self.source.config().plate_file.clone(),
+            plate_len: self.plate_content().map_or(0, |plate| plate.len()),
+            assets,
+            fonts,
+            referenced_assets,
+            diagnostics,
+        }
+    }
+
+    /// Every diagnostic for `markdown` in one list, without producing
+    /// artifacts: parse errors and warnings, the pre-compile checks of
+    /// [`Quill::explain`], and the backend's compile errors and warnings.
+    ///
+    /// Never fails; a parse error ends the list early since nothing after it
+    /// can run. Located diagnostics come first, ordered by file, line, and
+    /// column; the rest follow in the order they were found.
+    pub fn diagnostics(&self, markdown: &str) -> Vec<Diagnostic> {
+        let doc = match self.parse(markdown) {
+            Ok(output) => output.document,
+            Err(e) => return e.diagnostics().into_iter().cloned().collect(),
+        };
+
+        let mut diagnostics = self.explain(&doc).diagnostics;
+        // A compile_data failure is already in the plan's diagnostics.
+        if let Ok(context) = self.prepare_render_context(&doc, &RenderOptions::default()) {
+            match self
+                .backend
+                .open(&context.plate_content, &self.source, &context.json_data)
+            {
+                Ok(session) => diagnostics.extend(session.warnings().iter().cloned()),
+                Err(e) => diagnostics.extend(e.diagnostics().into_iter().cloned()),
+            }
+        }
+
+        diagnostics.sort_by(|a, b| match (&a.location, &b.location) {
+            (Some(a), Some(b)) => (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        diagnostics
     }
 
     /// Open an iterative render session for this document.
     pub fn open(&self, doc: &Document) -> Result<RenderSession, RenderError> {
-        let context = self.prepare_render_context(doc)?;
-        let warnings: Vec<_> = self.ref_mismatch_warning(doc).into_iter().collect();
-        let session =
-            self.backend
-                .open(&context.plate_content, &self.source, &context.json_data)?;
+        let context = self.prepare_render_context(doc, &RenderOptions::default())?;
+        self.open_prepared(doc, &context)
+    }
+
+    fn open_prepared(
+        &self,
+        doc: &Document,
+        context: &PreparedRenderContext,
+    ) -> Result<RenderSession, RenderError> {
+        let session = match context.output_format {
+            Some(format) => self.backend.open_for_format(
+                &context.plate_content,
+                &self.source,
+                &context.json_data,
+                format,
+            )?,
+            None => self
+                .backend
+                .open(&context.plate_content, &self.source, &context.json_data)?,
+        };
+        let mut warnings = session.warnings().to_vec();
+        warnings.extend(self.ref_mismatch_warning(doc));
         Ok(session.with_warnings(warnings))
     }
 
-    fn resolve_options(&self, opts: &RenderOptions) -> RenderOptions {
-        let output_format = opts
-            .output_format
-            .or_else(|| self.backend.supported_formats().first().copied());
-        RenderOptions {
-            output_format,
-            ppi: opts.ppi,
-            pages: opts.pages.clone(),
+    fn resolve_options(&self, opts: &RenderOptions) -> Result<RenderOptions, RenderError> {
+        let supported = self.output_formats();
+        if supported.is_empty() {
+            return Err(RenderError::FormatNotSupported {
+                diag: Box::new(
+                    Diagnostic::new(
+                        Severity::Error,
+                        format!(
+                            "Quill '{}' declares no output format its '{}' backend supports",
+                            self.name(),
+                            self.backend_id()
+                        ),
+                    )
+                    .with_code("quill::format_not_supported".to_string())
+                    .with_hint(format!(
+                        "Backend formats: {:?}",
+                        self.backend.supported_formats()
+                    )),
+                ),
+            });
+        }
+        let output_format = opts.output_format.or_else(|| supported.first().copied());
+        if let Some(format) = output_format {
+            if !supported.contains(&format) {
+                return Err(RenderError::FormatNotSupported {
+                    diag: Box::new(
+                        Diagnostic::new(
+                            Severity::Error,
+                            format!(
+                                "Quill '{}' does not support {:?} output",
+                                self.name(),
+                                format
+                            ),
+                        )
+                        .with_code("quill::format_not_supported".to_string())
+                        .with_hint(format!("Supported formats: {:?}", supported)),
+                    ),
+                });
+            }
         }
+        let mut resolved = opts.clone();
+        resolved.output_format = output_format;
+        Ok(resolved)
     }
 
     /// Compile a Document to JSON data suitable for the backend.
@@ -94,6 +683,15 @@ impl Quill {
     /// Applies coercion, validation, normalization, and schema defaults, then
     /// calls [`Document::to_plate_json`] to produce the wire format.
     pub fn compile_data(&self, doc: &Document) -> Result<serde_json::Value, RenderError> {
+        // Fill an empty main body from the quill's body template.
+        let filled;
+        let doc = if doc.main().body().trim().is_empty() && self.source.body_template().is_some() {
+            filled = self.with_body_template(doc)?;
+            &filled
+        } else {
+            doc
+        };
+
         // Coerce main-card frontmatter fields against the schema.
         let main_fields_map = doc.main().frontmatter().to_index_map();
         let coerced_frontmatter = self
@@ -183,13 +781,31 @@ impl Quill {
         Ok(final_doc.to_plate_json())
     }
 
-    fn prepare_render_context(&self, doc: &Document) -> Result<PreparedRenderContext, RenderError> {
+    fn prepare_render_context(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Result<PreparedRenderContext, RenderError> {
         Ok(PreparedRenderContext {
-            json_data: self.compile_data(doc)?,
+            json_data: self.render_data(doc, opts)?,
             plate_content: self.plate_content().unwrap_or_default(),
+            output_format: opts.output_format,
         })
     }
 
+    /// [`Quill::compile_data`] plus the per-render `SEED` from `opts`.
+    fn render_data(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+    ) -> Result<serde_json::Value, RenderError> {
+        let mut data = self.compile_data(doc)?;
+        if let (Some(seed), Some(map)) = (opts.seed, data.as_object_mut()) {
+            map.insert("SEED".to_string(), seed.into());
+        }
+        Ok(data)
+    }
+
     fn ref_mismatch_warning(&self, doc: &Document) -> Option<Diagnostic> {
         let doc_ref = doc.quill_reference().name.as_str();
         if doc_ref != self.source.name() {
@@ -213,6 +829,31 @@ impl Quill {
         }
     }
 
+    /// Warn when `doc` has no body text in its main card or any card, since
+    /// such a document usually renders as blank pages. Suppressed by
+    /// [`RenderOptions::allow_empty_body`].
+    fn push_empty_body_warning(
+        &self,
+        doc: &Document,
+        opts: &RenderOptions,
+        result: &mut RenderResult,
+    ) {
+        let has_body = std::iter::once(doc.main())
+            .chain(doc.cards())
+            .any(|card| !card.body().trim().is_empty());
+        if opts.allow_empty_body || has_body || self.source.body_template().is_some() {
+            return;
+        }
+        result.warnings.push(
+            Diagnostic::new(Severity::Warning, "document body is empty".to_string())
+                .with_code("quill::empty_body".to_string())
+                .with_hint(
+                    "add body text, or set `allow_empty_body` if the plate renders from fields alone"
+                        .to_string(),
+                ),
+        );
+    }
+
     fn apply_frontmatter_defaults(
         &self,
         frontmatter: &IndexMap<String, QuillValue>,
@@ -370,3 +1011,35 @@ impl std::fmt::Debug for Quill {
             .finish()
     }
 }
+
+/// Drop cards whose type is not in [`RenderOptions::include_cards`].
+/// [`RenderError::OutputFailed`] for a failed write of rendered output.
+fn output_failed(err: &std::io::Error) -> RenderError {
+    RenderError::OutputFailed {
+        diag: Box::new(
+            Diagnostic::new(
+                Severity::Error,
+                format!("Failed to write rendered output: {}", err),
+            )
+            .with_code("quill::output_write".to_string())
+            .with_source(err),
+        ),
+    }
+}
+
+fn select_cards<'a>(doc: &'a Document, opts: &RenderOptions) -> Cow<'a, Document> {
+    let Some(include) = &opts.include_cards else {
+        return Cow::Borrowed(doc);
+    };
+    let cards = doc
+        .cards()
+        .iter()
+        .filter(|card| include.contains(&card.tag()))
+        .cloned()
+        .collect();
+    Cow::Owned(Document::from_main_and_cards(
+        doc.main().clone(),
+        cards,
+        doc.warnings().to_vec(),
+    ))
+}
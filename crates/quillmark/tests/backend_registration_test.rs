@@ -1,10 +1,13 @@
 //! # Backend Registration Tests
 
-use quillmark::{Document, OutputFormat, Quillmark, RenderError};
+use quillmark::{Document, OutputFormat, Quill, Quillmark, RenderError};
 use quillmark_core::{
-    session::SessionHandle, Artifact, Backend, QuillSource, RenderOptions, RenderResult,
+    session::SessionHandle, Artifact, Backend, Diagnostic, FileTreeNode, QuillSource,
+    RenderOptions, RenderResult, Severity,
 };
 use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use tempfile::TempDir;
 
 #[derive(Debug)]
@@ -39,10 +42,19 @@ struct MockSession {
 }
 
 impl SessionHandle for MockSession {
-    fn render(&self, _opts: &RenderOptions) -> Result<RenderResult, RenderError> {
+    fn render(&self, opts: &RenderOptions) -> Result<RenderResult, RenderError> {
+        if let Some(format) = opts.output_format.filter(|f| *f != OutputFormat::Txt) {
+            return Err(RenderError::FormatNotSupported {
+                diag: Box::new(Diagnostic::new(
+                    Severity::Error,
+                    format!("{:?} not supported by mock backend", format),
+                )),
+            });
+        }
         let artifacts = vec![Artifact {
             bytes: self.bytes.clone(),
             output_format: OutputFormat::Txt,
+            page: None,
         }];
         Ok(RenderResult::new(artifacts, OutputFormat::Txt))
     }
@@ -56,6 +68,26 @@ impl SessionHandle for MockSession {
     }
 }
 
+#[test]
+fn test_mock_backend_conformance() {
+    quillmark_core::testing::assert_backend_conformance(&MockBackend { id: "mock" });
+}
+
+#[test]
+fn test_builder_without_default_backends_is_empty() {
+    let engine = Quillmark::builder().without_default_backends().build();
+    assert!(engine.registered_backends().is_empty());
+}
+
+#[test]
+fn test_builder_with_backend_registers_only_that_backend() {
+    let engine = Quillmark::builder()
+        .without_default_backends()
+        .with_backend(Box::new(MockBackend { id: "mock" }))
+        .build();
+    assert_eq!(engine.registered_backends(), vec!["mock"]);
+}
+
 #[test]
 fn test_register_backend_basic() {
     let mut engine = Quillmark::new();
@@ -110,10 +142,9 @@ fn test_render_with_custom_backend() {
     let result = quill
         .render(
             &parsed,
-            &RenderOptions {
-                output_format: Some(OutputFormat::Txt),
-                ..Default::default()
-            },
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Txt)
+                .build(),
         )
         .expect("render failed");
 
@@ -121,6 +152,74 @@ fn test_render_with_custom_backend() {
     assert_eq!(result.artifacts[0].output_format, OutputFormat::Txt);
 }
 
+#[test]
+fn test_render_fails_when_declared_formats_miss_backend() {
+    let mut engine = Quillmark::new();
+    engine.register_backend(Box::new(MockBackend { id: "mock-txt" }));
+
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("pdf_only");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        "quill:\n  name: \"pdf_only\"\n  version: \"1.0\"\n  backend: \"mock-txt\"\n  plate_file: \"plate.txt\"\n  description: \"Test\"\n  formats: [pdf]\n",
+    )
+    .unwrap();
+    fs::write(quill_path.join("plate.txt"), "plate").unwrap();
+
+    let quill = engine.quill_from_path(&quill_path).unwrap();
+    assert!(quill.output_formats().is_empty());
+
+    let doc = Document::from_markdown("---\nQUILL: pdf_only\n---\n").unwrap();
+    match quill.render(&doc, &RenderOptions::default()) {
+        Err(RenderError::FormatNotSupported { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("quill::format_not_supported"));
+        }
+        other => panic!("Expected FormatNotSupported, got: {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_quill_from_parts_reuses_backend() {
+    let engine = Quillmark::builder()
+        .without_default_backends()
+        .with_backend(Box::new(MockBackend { id: "mock-txt" }))
+        .build();
+    let yaml = "quill:\n  name: \"parts\"\n  version: \"1.0\"\n  backend: \"mock-txt\"\n  plate_file: \"plate.txt\"\n  description: \"Test\"\n";
+    let tree = |plate: &str| {
+        let mut files = std::collections::HashMap::new();
+        files.insert(
+            "Quill.yaml".to_string(),
+            FileTreeNode::File {
+                contents: yaml.as_bytes().to_vec(),
+            },
+        );
+        files.insert(
+            "plate.txt".to_string(),
+            FileTreeNode::File {
+                contents: plate.as_bytes().to_vec(),
+            },
+        );
+        FileTreeNode::Directory { files }
+    };
+    let quill = engine.quill(tree("first")).unwrap();
+    let doc = Document::from_markdown("---\nQUILL: parts\n---\n\nBody\n").unwrap();
+
+    let (_, backend) = quill.into_parts();
+    let source = Arc::new(QuillSource::from_tree(tree("second")).unwrap());
+    let rebuilt = Quill::from_parts(Arc::clone(&source), backend).unwrap();
+    let result = rebuilt.render(&doc, &RenderOptions::default()).unwrap();
+    assert_eq!(result.artifacts[0].bytes, b"second");
+
+    let other: Arc<dyn Backend> = Arc::new(MockBackend { id: "other" });
+    match Quill::from_parts(source, other) {
+        Err(RenderError::UnsupportedBackend { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("quill::backend_mismatch"));
+        }
+        other => panic!("Expected UnsupportedBackend, got: {:?}", other.err()),
+    }
+}
+
 #[test]
 fn test_register_backend_after_new() {
     let mut engine = Quillmark::new();
@@ -130,3 +229,142 @@ fn test_register_backend_after_new() {
     assert_eq!(backends.len(), initial_count + 1);
     assert!(backends.contains(&"added-later"));
 }
+
+/// Backend that, like a form-filling backend, needs a `form.pdf` in the quill.
+#[derive(Debug)]
+struct FormBackend;
+
+impl Backend for FormBackend {
+    fn id(&self) -> &'static str {
+        "form"
+    }
+
+    fn supported_formats(&self) -> &'static [OutputFormat] {
+        &[OutputFormat::Txt]
+    }
+
+    fn open(
+        &self,
+        plated: &str,
+        source: &QuillSource,
+        json_data: &serde_json::Value,
+    ) -> Result<quillmark::RenderSession, RenderError> {
+        MockBackend { id: "form" }.open(plated, source, json_data)
+    }
+
+    fn validate_quill(&self, source: &QuillSource) -> Result<(), RenderError> {
+        if source.file_exists("form.pdf") {
+            Ok(())
+        } else {
+            Err(RenderError::QuillConfig {
+                diag: Box::new(
+                    Diagnostic::new(Severity::Error, "Quill is missing form.pdf".to_string())
+                        .with_code("form::missing_form".to_string()),
+                ),
+            })
+        }
+    }
+}
+
+#[test]
+fn test_backend_validate_quill_runs_at_load() {
+    let engine = Quillmark::builder()
+        .without_default_backends()
+        .with_backend(Box::new(FormBackend))
+        .build();
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("form_quill");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        "quill:\n  name: \"form_quill\"\n  version: \"1.0\"\n  backend: \"form\"\n  description: \"Test\"\n",
+    )
+    .unwrap();
+
+    match engine.quill_from_path(&quill_path) {
+        Err(RenderError::QuillConfig { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("form::missing_form"));
+            assert!(diag.message.contains("form.pdf"));
+        }
+        other => panic!("Expected QuillConfig error, got: {:?}", other.err()),
+    }
+
+    fs::write(quill_path.join("form.pdf"), b"%PDF-1.7").unwrap();
+    engine
+        .quill_from_path(&quill_path)
+        .expect("quill with form.pdf should load");
+}
+
+/// Mock backend that counts how many sessions it opened.
+#[derive(Debug)]
+struct CountingBackend {
+    opens: Arc<AtomicUsize>,
+}
+
+impl Backend for CountingBackend {
+    fn id(&self) -> &'static str {
+        "counting"
+    }
+
+    fn supported_formats(&self) -> &'static [OutputFormat] {
+        &[OutputFormat::Txt]
+    }
+
+    fn open(
+        &self,
+        plated: &str,
+        source: &QuillSource,
+        json_data: &serde_json::Value,
+    ) -> Result<quillmark::RenderSession, RenderError> {
+        self.opens.fetch_add(1, Ordering::SeqCst);
+        MockBackend { id: "counting" }.open(plated, source, json_data)
+    }
+}
+
+#[test]
+fn test_render_incremental_reuses_or_falls_back() {
+    let opens = Arc::new(AtomicUsize::new(0));
+    let engine = Quillmark::builder()
+        .without_default_backends()
+        .with_backend(Box::new(CountingBackend {
+            opens: Arc::clone(&opens),
+        }))
+        .build();
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = temp_dir.path().join("catalog");
+    fs::create_dir_all(&quill_path).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        "quill:\n  name: \"catalog\"\n  version: \"1.0\"\n  backend: \"counting\"\n  plate_file: \"plate.txt\"\n  description: \"Test\"\ncard_types:\n  item:\n    fields:\n      name:\n        type: string\n",
+    )
+    .unwrap();
+    fs::write(quill_path.join("plate.txt"), "catalog").unwrap();
+    let quill = engine.quill_from_path(&quill_path).unwrap();
+
+    let old = Document::from_markdown("---\nQUILL: catalog\n---\n\n---\nCARD: item\n---\n\nOne\n")
+        .unwrap();
+    let new = Document::from_markdown("---\nQUILL: catalog\n---\n\n---\nCARD: item\n---\n\nTwo\n")
+        .unwrap();
+    let opts = RenderOptions::default();
+    let old_result = quill.render(&old, &opts).unwrap();
+    assert_eq!(opens.load(Ordering::SeqCst), 1);
+
+    let same = quill
+        .render_incremental(&old, &old.clone(), &old_result, &opts)
+        .unwrap();
+    assert_eq!(same.artifacts[0].bytes, old_result.artifacts[0].bytes);
+    assert_eq!(
+        opens.load(Ordering::SeqCst),
+        1,
+        "unchanged document reuses result"
+    );
+
+    quill
+        .render_incremental(&old, &new, &old_result, &opts)
+        .unwrap();
+    assert_eq!(
+        opens.load(Ordering::SeqCst),
+        2,
+        "changed card falls back to full render"
+    );
+}
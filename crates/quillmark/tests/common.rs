@@ -51,10 +51,9 @@ pub fn demo(
     // render output
     let rendered = quill.render(
         &parsed,
-        &quillmark_core::RenderOptions {
-            output_format: Some(quillmark_core::OutputFormat::Pdf),
-            ..Default::default()
-        },
+        &quillmark_core::RenderOptions::builder()
+            .output_format(quillmark_core::OutputFormat::Pdf)
+            .build(),
     )?;
     let output_bytes = rendered.artifacts[0].bytes.clone();
 
@@ -3,7 +3,7 @@
 use std::fs;
 use tempfile::TempDir;
 
-use quillmark::{Document, OutputFormat, Quillmark, RenderOptions};
+use quillmark::{Document, OutputFormat, QuillIgnore, Quillmark, RenderOptions, Severity};
 
 fn make_quill_dir(temp_dir: &TempDir, name: &str, backend: &str) -> std::path::PathBuf {
     let quill_path = temp_dir.path().join(name);
@@ -45,6 +45,50 @@ fn test_quill_from_path_engine_metadata() {
     assert!(quill.supported_formats().contains(&OutputFormat::Pdf));
 }
 
+#[test]
+#[cfg(feature = "typst")]
+fn test_quill_formats_declaration_narrows_supported_formats() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "form_quill", "typst");
+    let yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!("{}  formats: [pdf]\n", yaml),
+    )
+    .unwrap();
+
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    assert_eq!(quill.output_formats(), vec![OutputFormat::Pdf]);
+    assert!(quill.supported_formats().contains(&OutputFormat::Svg));
+
+    let parsed = Document::from_markdown("---\nQUILL: form_quill\n---\n").unwrap();
+    let result = quill.render(
+        &parsed,
+        &RenderOptions::builder()
+            .output_format(OutputFormat::Svg)
+            .build(),
+    );
+    match result {
+        Err(quillmark::RenderError::FormatNotSupported { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("quill::format_not_supported"));
+        }
+        other => panic!("Expected FormatNotSupported, got: {:?}", other.err()),
+    }
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_quill_without_formats_declaration_uses_backend_formats() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "open_quill", "typst");
+
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let formats = quill.output_formats();
+    for format in [OutputFormat::Pdf, OutputFormat::Svg, OutputFormat::Png] {
+        assert!(formats.contains(&format), "missing {:?}", format);
+    }
+}
+
 #[test]
 fn test_quill_engine_backend_not_found() {
     let temp_dir = TempDir::new().unwrap();
@@ -101,10 +145,9 @@ fn test_quill_render_succeeds_with_engine_loaded_quill() {
     let parsed = Document::from_markdown("---\nQUILL: my_quill\n---\n").expect("parse failed");
     let result = quill.render(
         &parsed,
-        &RenderOptions {
-            output_format: Some(OutputFormat::Pdf),
-            ..Default::default()
-        },
+        &RenderOptions::builder()
+            .output_format(OutputFormat::Pdf)
+            .build(),
     );
 
     if let Err(quillmark::RenderError::EngineCreation { diag }) = &result {
@@ -117,3 +160,967 @@ fn test_quill_render_succeeds_with_engine_loaded_quill() {
         "render should succeed for engine-loaded quill"
     );
 }
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_with_overlay_prepends_cover_pages() {
+    let temp_dir = TempDir::new().unwrap();
+    let cover_path = make_quill_dir(&temp_dir, "cover", "typst");
+    let body_path = make_quill_dir(&temp_dir, "body", "typst");
+    fs::write(
+        body_path.join("plate.typ"),
+        "First page\n#pagebreak()\nSecond page",
+    )
+    .unwrap();
+
+    let engine = Quillmark::new();
+    let cover = engine.quill_from_path(cover_path).unwrap();
+    let body = engine.quill_from_path(body_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: body\n---\n").unwrap();
+
+    let pdf = body
+        .render_with_overlay(
+            &parsed,
+            &cover,
+            None,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Pdf)
+                .build(),
+        )
+        .expect("overlay render failed");
+    assert_eq!(pdf.artifacts.len(), 1);
+    assert!(pdf.artifacts[0].bytes.starts_with(b"%PDF"));
+
+    let svg = body
+        .render_with_overlay(
+            &parsed,
+            &cover,
+            None,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Svg)
+                .build(),
+        )
+        .expect("overlay render failed");
+    assert_eq!(svg.artifacts.len(), 3);
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_max_pages_limit() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "long", "typst");
+    fs::write(
+        quill_path.join("plate.typ"),
+        "One\n#pagebreak()\nTwo\n#pagebreak()\nThree",
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: long\n---\n").unwrap();
+
+    let capped = quill.render(
+        &parsed,
+        &RenderOptions::builder()
+            .output_format(OutputFormat::Pdf)
+            .max_pages(2)
+            .build(),
+    );
+    match capped {
+        Err(quillmark::RenderError::TooManyPages { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("render::too_many_pages"));
+            assert!(diag.message.contains("limit of 2"));
+        }
+        other => panic!("Expected TooManyPages, got: {:?}", other.err()),
+    }
+
+    let result = quill
+        .render(
+            &parsed,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Pdf)
+                .max_pages(10)
+                .build(),
+        )
+        .expect("render under the page limit failed");
+    assert!(result.artifacts[0].bytes.starts_with(b"%PDF"));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_cache_hits_and_misses() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "cached", "typst");
+    fs::create_dir_all(quill_path.join("assets")).unwrap();
+    fs::write(quill_path.join("assets/note.txt"), "v1").unwrap();
+
+    let engine = Quillmark::builder().render_cache(8).build();
+    let parsed = Document::from_markdown("---\nQUILL: cached\n---\n\nBody").unwrap();
+    let pdf = RenderOptions::builder()
+        .output_format(OutputFormat::Pdf)
+        .build();
+    let stats = |engine: &Quillmark| {
+        let stats = engine.render_cache_stats().unwrap();
+        (stats.hits, stats.misses)
+    };
+
+    let quill = engine.quill_from_path(&quill_path).unwrap();
+    let first = quill.render(&parsed, &pdf).unwrap();
+    let second = quill.render(&parsed, &pdf).unwrap();
+    assert_eq!(stats(&engine), (1, 1));
+    assert_eq!(first.artifacts[0].bytes, second.artifacts[0].bytes);
+
+    // A different output format compiles again.
+    quill
+        .render(
+            &parsed,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Svg)
+                .build(),
+        )
+        .unwrap();
+    assert_eq!(stats(&engine), (1, 2));
+
+    // Editing an asset changes the quill fingerprint, so the reloaded quill misses.
+    fs::write(quill_path.join("assets/note.txt"), "v2").unwrap();
+    let edited = engine.quill_from_path(&quill_path).unwrap();
+    edited.render(&parsed, &pdf).unwrap();
+    assert_eq!(stats(&engine), (1, 3));
+
+    assert!(Quillmark::new().render_cache_stats().is_none());
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_fragment_uses_fragment_plate() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "wrapped", "typst");
+    let quill_yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!("{}  fragment_plate_file: \"fragment.typ\"\n", quill_yaml),
+    )
+    .unwrap();
+    let body = "#import \"@local/quillmark-helper:0.1.0\": data\n#data.BODY";
+    fs::write(
+        quill_path.join("plate.typ"),
+        format!("Letterhead\n#pagebreak()\n{}", body),
+    )
+    .unwrap();
+    fs::write(quill_path.join("fragment.typ"), body).unwrap();
+
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: wrapped\n---\n\nJust the section.").unwrap();
+    let svg = RenderOptions::builder()
+        .output_format(OutputFormat::Svg)
+        .build();
+
+    let full = quill.render(&parsed, &svg).expect("full render failed");
+    assert_eq!(full.artifacts.len(), 2);
+    let fragment = quill
+        .render_fragment(&parsed, &svg)
+        .expect("fragment render failed");
+    assert_eq!(
+        fragment.artifacts.len(),
+        1,
+        "wrapper page should be omitted"
+    );
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_fragment_without_fragment_plate_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "plain", "typst");
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: plain\n---\n\nBody").unwrap();
+
+    match quill.render_fragment(&parsed, &RenderOptions::default()) {
+        Err(quillmark::RenderError::QuillConfig { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("quill::no_fragment_plate"));
+        }
+        other => panic!("Expected QuillConfig error, got: {:?}", other.err()),
+    }
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_timed_reports_stages() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "timed", "typst");
+    fs::write(quill_path.join("plate.typ"), "A\n#pagebreak()\nB").unwrap();
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: timed\n---\n\nBody").unwrap();
+
+    let (result, timings) = quill
+        .render_timed(&parsed, &RenderOptions::default())
+        .expect("timed render failed");
+    assert_eq!(timings.page_count, 2);
+    assert!(timings.compile > std::time::Duration::ZERO);
+    assert!(timings.output > std::time::Duration::ZERO);
+    assert!(!result.artifacts.is_empty());
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_missing_font_falls_back_with_warning() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "fonty", "typst");
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#set text(font: \"Nonexistent Sans\")\nHello",
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: fonty\n---\n\nBody").unwrap();
+
+    let result = quill
+        .render(&parsed, &RenderOptions::default())
+        .expect("render should fall back to a default font");
+    assert!(!result.artifacts.is_empty());
+    let warning = result
+        .warnings
+        .iter()
+        .find(|w| w.code.as_deref() == Some("typst::missing_font"))
+        .expect("missing font warning");
+    assert!(warning.message.to_lowercase().contains("nonexistent sans"));
+
+    let strict = RenderOptions::builder().strict_fonts(true).build();
+    match quill.render(&parsed, &strict) {
+        Err(quillmark::RenderError::CompilationFailed { diags }) => {
+            assert_eq!(diags[0].code.as_deref(), Some("typst::missing_font"));
+            assert!(diags[0].message.to_lowercase().contains("nonexistent sans"));
+        }
+        other => panic!("Expected CompilationFailed, got: {:?}", other.err()),
+    }
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_explain_reports_plan_without_compiling() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "planned", "typst");
+    fs::create_dir_all(quill_path.join("assets/fonts")).unwrap();
+    fs::write(quill_path.join("assets/logo.svg"), "<svg/>").unwrap();
+    fs::write(quill_path.join("assets/fonts/Body.ttf"), "not a real font").unwrap();
+    // Would fail to compile: the second image does not exist.
+    let plate = "#image(\"assets/logo.svg\")\n#image(\"assets/missing.png\")";
+    fs::write(quill_path.join("plate.typ"), plate).unwrap();
+
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: planned\n---\n\nBody").unwrap();
+    let plan = quill.explain(&parsed);
+
+    assert_eq!(plan.backend, "typst");
+    assert_eq!(plan.quill, "planned");
+    assert_eq!(plan.plate_len, plate.len());
+    assert_eq!(plan.fingerprint, quill.source().fingerprint());
+    assert_eq!(plan.assets, vec!["assets/logo.svg"]);
+    assert_eq!(plan.fonts, vec!["assets/fonts/Body.ttf"]);
+    assert!(plan.diagnostics.iter().any(|d| d.code.as_deref()
+        == Some("quill::missing_referenced_asset")
+        && d.message.contains("assets/missing.png")));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_diagnostics_aggregates_lints_and_compile_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "checked", "typst");
+    // The uncalled image is a plate lint; the unknown variable fails to compile.
+    let plate = "#let logo() = image(\"assets/missing.png\")\n#undefined_name";
+    fs::write(quill_path.join("plate.typ"), plate).unwrap();
+    let quill = Quillmark::new().quill_from_path(quill_path).unwrap();
+
+    let diagnostics = quill.diagnostics("---\nQUILL: checked\n---\n\nBody");
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.code.as_deref() == Some("quill::missing_referenced_asset")));
+    let compile_error = diagnostics
+        .iter()
+        .find(|d| d.severity == Severity::Error && d.message.contains("undefined_name"))
+        .expect("compile error should be reported");
+    assert!(compile_error.location.is_some());
+    // Located diagnostics sort ahead of unlocated ones.
+    assert!(diagnostics[0].location.is_some());
+
+    let diagnostics = quill.diagnostics("---\nQUILL: [bad\n---\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_pdf_only_asset_is_not_loaded_for_svg() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "print_only", "typst");
+    fs::create_dir_all(quill_path.join("assets")).unwrap();
+    fs::write(quill_path.join("assets/print.txt"), "print only").unwrap();
+    let quill_yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!(
+            "{}assets:\n  assets/print.txt:\n    formats: [pdf]\n",
+            quill_yaml
+        ),
+    )
+    .unwrap();
+    fs::write(quill_path.join("plate.typ"), "#read(\"assets/print.txt\")").unwrap();
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: print_only\n---\n\nBody").unwrap();
+
+    let pdf = quill
+        .render(
+            &parsed,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Pdf)
+                .build(),
+        )
+        .expect("PDF render should load the asset");
+    assert!(pdf.artifacts[0].bytes.starts_with(b"%PDF"));
+
+    let Err(err) = quill.render(
+        &parsed,
+        &RenderOptions::builder()
+            .output_format(OutputFormat::Svg)
+            .build(),
+    ) else {
+        panic!("SVG render should not see the PDF-only asset");
+    };
+    assert!(
+        err.diagnostics()
+            .iter()
+            .any(|d| d.message.contains("print.txt")),
+        "{:?}",
+        err.diagnostics()
+    );
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_typst_rejects_non_typst_plate_at_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "mislabeled", "typst");
+    let engine = Quillmark::new();
+    engine
+        .quill_from_path(&quill_path)
+        .expect("a .typ plate should load");
+
+    let quill_yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        quill_yaml.replace("plate.typ", "plate.txt"),
+    )
+    .unwrap();
+    fs::write(quill_path.join("plate.txt"), "#rect(width: 1cm)").unwrap();
+    match engine.quill_from_path(&quill_path) {
+        Err(quillmark::RenderError::QuillConfig { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("typst::invalid_plate_file"));
+            assert!(diag.message.contains("plate.txt"));
+        }
+        other => panic!("Expected QuillConfig error, got: {:?}", other.err()),
+    }
+}
+
+fn make_body_template_quill(temp_dir: &TempDir) -> std::path::PathBuf {
+    let quill_path = make_quill_dir(temp_dir, "letter", "typst");
+    let quill_yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!("{}  body_template_file: \"body.md\"\n", quill_yaml),
+    )
+    .unwrap();
+    fs::write(
+        quill_path.join("body.md"),
+        "Dear {{ name }},\n\nYou owe {{amount}}. {{ missing }}",
+    )
+    .unwrap();
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#import \"@local/quillmark-helper:0.1.0\": data\n#data.BODY",
+    )
+    .unwrap();
+    quill_path
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_body_template_fills_field_only_document() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill = Quillmark::new()
+        .quill_from_path(make_body_template_quill(&temp_dir))
+        .unwrap();
+    let parsed =
+        Document::from_markdown("---\nQUILL: letter\nname: Ada\namount: 42\n---\n").unwrap();
+
+    let data = quill.compile_data(&parsed).unwrap();
+    assert_eq!(data["BODY"], "Dear Ada,\n\nYou owe 42. {{ missing }}");
+
+    let result = quill
+        .render(&parsed, &RenderOptions::default())
+        .expect("render failed");
+    assert!(!result.artifacts.is_empty());
+    assert!(
+        result
+            .warnings
+            .iter()
+            .all(|w| w.code.as_deref() != Some("quill::empty_body")),
+        "template-filled document should not warn about an empty body"
+    );
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_explicit_body_overrides_body_template() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill = Quillmark::new()
+        .quill_from_path(make_body_template_quill(&temp_dir))
+        .unwrap();
+    let parsed =
+        Document::from_markdown("---\nQUILL: letter\nname: Ada\n---\n\nHand-written body.")
+            .unwrap();
+
+    let data = quill.compile_data(&parsed).unwrap();
+    assert_eq!(data["BODY"], "\nHand-written body.");
+
+    let forced = quill
+        .compile_data(&quill.with_body_template(&parsed).unwrap())
+        .unwrap();
+    assert!(forced["BODY"].as_str().unwrap().starts_with("Dear Ada,"));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_with_overlay_rejects_unsupported_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "body", "typst");
+    let yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!("{}  formats: [pdf]\n", yaml),
+    )
+    .unwrap();
+
+    let engine = Quillmark::new();
+    let quill = engine.quill_from_path(quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: body\n---\n").unwrap();
+
+    let result = quill.render_with_overlay(
+        &parsed,
+        &quill,
+        None,
+        &RenderOptions::builder()
+            .output_format(OutputFormat::Svg)
+            .build(),
+    );
+    assert!(matches!(
+        result,
+        Err(quillmark::RenderError::FormatNotSupported { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_with_data_matches_compile_data() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "data_quill", "typst");
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed =
+        Document::from_markdown("---\nQUILL: data_quill\ntitle: Hello\n---\n\nBody\n").unwrap();
+
+    let (result, data) = quill
+        .render_with_data(
+            &parsed,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Pdf)
+                .build(),
+        )
+        .expect("render_with_data failed");
+    assert_eq!(data, quill.compile_data(&parsed).unwrap());
+    assert_eq!(data["title"], "Hello");
+    assert!(result.artifacts[0].bytes.starts_with(b"%PDF"));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_include_cards_filters_card_types() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "catalog", "typst");
+    let yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!("{}card_types:\n  products: {{}}\n  reviews: {{}}\n", yaml),
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed = Document::from_markdown(
+        "---\nQUILL: catalog\n---\n\nBody\n\n---\nCARD: products\n---\n\nWidget\n\n---\nCARD: reviews\n---\n\nGreat\n",
+    )
+    .unwrap();
+    let card_tags = |include_cards: Option<&[&str]>| {
+        let opts = match include_cards {
+            Some(cards) => RenderOptions::builder().include_cards(cards.iter().copied()),
+            None => RenderOptions::builder(),
+        };
+        let (_, data) = quill
+            .render_with_data(&parsed, &opts.build())
+            .expect("render_with_data failed");
+        data["CARDS"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|card| card["CARD"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(card_tags(Some(&["products", "absent"])), vec!["products"]);
+    assert_eq!(card_tags(None), vec!["products", "reviews"]);
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_to_writer_streams_pdf() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "streamed", "typst");
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#set document(date: datetime(year: 2024, month: 1, day: 1))\nHello\n#pagebreak()\nWorld",
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: streamed\n---\n\nBody\n").unwrap();
+
+    let buffered = quill.render(&parsed, &RenderOptions::default()).unwrap();
+    let mut written = Vec::new();
+    quill
+        .render_to_writer(&parsed, &RenderOptions::default(), &mut written)
+        .unwrap();
+    assert_eq!(written, buffered.artifacts[0].bytes);
+
+    let mut archived = Vec::new();
+    quill
+        .render_to_writer(
+            &parsed,
+            &RenderOptions::builder()
+                .pdf_standard(quillmark::PdfStandard::A2b)
+                .build(),
+            &mut archived,
+        )
+        .unwrap();
+    assert!(String::from_utf8_lossy(&archived).contains("pdfaid:part"));
+
+    struct BrokenPipe;
+    impl std::io::Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    match quill.render_to_writer(&parsed, &RenderOptions::default(), &mut BrokenPipe) {
+        Err(quillmark::RenderError::OutputFailed { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("quill::output_write"));
+        }
+        other => panic!("Expected OutputFailed, got: {:?}", other),
+    }
+
+    let svg = RenderOptions::builder()
+        .output_format(OutputFormat::Svg)
+        .build();
+    match quill.render_to_writer(&parsed, &svg, &mut Vec::new()) {
+        Err(quillmark::RenderError::FormatNotSupported { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("quill::multiple_artifacts"));
+        }
+        other => panic!("Expected FormatNotSupported, got: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_each_renders_one_output_per_card() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "mailer", "typst");
+    let yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!("{}card_types:\n  recipients: {{}}\n", yaml),
+    )
+    .unwrap();
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#import \"@local/quillmark-helper:0.1.0\": data\n#data.sender: #for card in data.CARDS [#card.name]",
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed = Document::from_markdown(
+        "---\nQUILL: mailer\nsender: Ada\n---\n\nBody\n\n---\nCARD: recipients\nname: Bea\n---\n\n---\nCARD: recipients\nname: Cal\n---\n\n---\nCARD: recipients\nname: Dee\n---\n",
+    )
+    .unwrap();
+
+    let pdfs = quill
+        .render_each(&parsed, "recipients", &RenderOptions::default())
+        .unwrap();
+    assert_eq!(pdfs.len(), 3);
+    for result in &pdfs {
+        assert_eq!(result.output_format, OutputFormat::Pdf);
+        assert!(result.artifacts[0].bytes.starts_with(b"%PDF"));
+    }
+
+    let texts = quill
+        .render_each(
+            &parsed,
+            "recipients",
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Txt)
+                .build(),
+        )
+        .unwrap()
+        .iter()
+        .map(|result| String::from_utf8(result.artifacts[0].bytes.clone()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(texts.len(), 3);
+    let names = ["Bea", "Cal", "Dee"];
+    for (text, name) in texts.iter().zip(names) {
+        assert!(text.contains("Ada"), "{}", text);
+        for other in names {
+            assert_eq!(text.contains(other), other == name, "{}", text);
+        }
+    }
+    assert!(quill
+        .render_each(&parsed, "absent", &RenderOptions::default())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_seed_reaches_plate() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "seeded", "typst");
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#import \"@local/quillmark-helper:0.1.0\": seed\n#if seed == none [unseeded] else [#calc.rem(seed * 7919, 1000)]",
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: seeded\n---\n\nBody\n").unwrap();
+    let render_text = |seed: Option<u64>| {
+        let result = quill
+            .render(
+                &parsed,
+                &match seed {
+                    Some(seed) => RenderOptions::builder().seed(seed),
+                    None => RenderOptions::builder(),
+                }
+                .output_format(OutputFormat::Txt)
+                .build(),
+            )
+            .unwrap();
+        String::from_utf8(result.artifacts[0].bytes.clone()).unwrap()
+    };
+
+    assert_eq!(render_text(Some(42)), render_text(Some(42)));
+    assert_eq!(render_text(Some(42)), "598");
+    assert_eq!(render_text(None), "unseeded");
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_all_formats_compiles_once_per_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "multi", "typst");
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: multi\n---\n\nBody\n").unwrap();
+
+    let results = quill
+        .render_all_formats(
+            &parsed,
+            &[OutputFormat::Pdf, OutputFormat::Svg],
+            &RenderOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[&OutputFormat::Pdf].artifacts[0]
+        .bytes
+        .starts_with(b"%PDF"));
+    let svg = String::from_utf8(results[&OutputFormat::Svg].artifacts[0].bytes.clone()).unwrap();
+    assert!(svg.contains("<svg"));
+
+    let limited_path = make_quill_dir(&temp_dir, "limited", "typst");
+    let yaml = fs::read_to_string(limited_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        limited_path.join("Quill.yaml"),
+        yaml.replace("  description:", "  formats: [pdf]\n  description:"),
+    )
+    .unwrap();
+    let limited = Quillmark::new().quill_from_path(&limited_path).unwrap();
+    assert!(matches!(
+        limited.render_all_formats(
+            &parsed,
+            &[OutputFormat::Pdf, OutputFormat::Svg],
+            &RenderOptions::default(),
+        ),
+        Err(quillmark::RenderError::FormatNotSupported { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_quill_from_path_with_custom_ignore() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "ignore_quill", "typst");
+    fs::create_dir_all(quill_path.join("build/out")).unwrap();
+    fs::write(quill_path.join("build/out/cache.bin"), "stale").unwrap();
+    fs::create_dir_all(quill_path.join("node_modules")).unwrap();
+    fs::write(quill_path.join("node_modules/dep.js"), "dep").unwrap();
+
+    let engine = Quillmark::new();
+    let supplemented = engine
+        .quill_from_path_with_ignore(
+            &quill_path,
+            QuillIgnore::defaults().with_patterns(["build/"]),
+        )
+        .unwrap();
+    let files = supplemented.source().files();
+    assert!(files.file_exists("plate.typ"));
+    assert!(!files.file_exists("build/out/cache.bin"));
+    assert!(!files.file_exists("node_modules/dep.js"));
+
+    let replaced = engine
+        .quill_from_path_with_ignore(&quill_path, QuillIgnore::new(vec!["build/".to_string()]))
+        .unwrap();
+    let files = replaced.source().files();
+    assert!(!files.file_exists("build/out/cache.bin"));
+    assert!(files.file_exists("node_modules/dep.js"));
+
+    let default = engine.quill_from_path(&quill_path).unwrap();
+    assert!(default.source().files().file_exists("build/out/cache.bin"));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_quill_extends_base_overrides_plate_and_inherits_assets() {
+    let temp_dir = TempDir::new().unwrap();
+    let base = make_quill_dir(&temp_dir, "base_quill", "typst");
+    fs::create_dir_all(base.join("assets")).unwrap();
+    fs::write(base.join("assets/logo.svg"), "<svg/>").unwrap();
+    fs::write(
+        base.join("Quill.yaml"),
+        "quill:\n  name: base_quill\n  version: \"1.0\"\n  backend: typst\n  plate_file: plate.typ\n  description: Base\n  author: Base Team\ntypst:\n  math: true\n",
+    )
+    .unwrap();
+
+    let child = temp_dir.path().join("child_quill");
+    fs::create_dir_all(&child).unwrap();
+    fs::write(
+        child.join("Quill.yaml"),
+        "quill:\n  name: child_quill\n  extends: ../base_quill\n  description: Child\n",
+    )
+    .unwrap();
+    fs::write(child.join("plate.typ"), "Child plate").unwrap();
+
+    let quill = Quillmark::new().quill_from_path(&child).unwrap();
+    let source = quill.source();
+    assert_eq!(quill.name(), "child_quill");
+    assert_eq!(source.plate(), Some("Child plate"));
+    assert!(source.files().file_exists("assets/logo.svg"));
+    assert_eq!(source.config().description, "Child");
+    assert_eq!(source.config().author, "Base Team");
+    assert_eq!(source.config().version, "1.0");
+    assert!(source.config().backend_config.contains_key("math"));
+    assert!(!source.metadata().contains_key("extends"));
+}
+
+#[test]
+fn test_quill_extends_cycle_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    for (name, base) in [("quill_a", "quill_b"), ("quill_b", "quill_a")] {
+        let dir = temp_dir.path().join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Quill.yaml"),
+            format!(
+                "quill:\n  name: {}\n  version: \"1.0\"\n  backend: typst\n  description: Test\n  extends: ../{}\n",
+                name, base
+            ),
+        )
+        .unwrap();
+    }
+
+    let Err(err) = Quillmark::new().quill_from_path(temp_dir.path().join("quill_a")) else {
+        panic!("cycle should fail");
+    };
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code.as_deref(), Some("quill::extends_cycle"));
+    assert!(diag.message.contains("quill_a -> "), "{}", diag.message);
+    assert!(diag.message.ends_with("quill_a"), "{}", diag.message);
+}
+
+#[test]
+fn test_quill_extends_depth_limit() {
+    use quillmark_core::error::MAX_EXPANSION_DEPTH;
+    let temp_dir = TempDir::new().unwrap();
+    // quill_0 extends quill_1, ... up to a root base with no `extends`.
+    let make_chain = |prefix: &str, depth: usize| {
+        for i in 0..=depth {
+            let dir = temp_dir.path().join(format!("{}_{}", prefix, i));
+            fs::create_dir_all(&dir).unwrap();
+            let extends = if i < depth {
+                format!("  extends: ../{}_{}\n", prefix, i + 1)
+            } else {
+                String::new()
+            };
+            fs::write(
+                dir.join("Quill.yaml"),
+                format!(
+                    "quill:\n  name: {}_{}\n  version: \"1.0\"\n  backend: typst\n  description: Test\n{}",
+                    prefix, i, extends
+                ),
+            )
+            .unwrap();
+        }
+        temp_dir.path().join(format!("{}_0", prefix))
+    };
+
+    let engine = Quillmark::new();
+    let ok = make_chain("ok", MAX_EXPANSION_DEPTH - 1);
+    assert_eq!(engine.quill_from_path(&ok).unwrap().name(), "ok_0");
+
+    let deep = make_chain("deep", MAX_EXPANSION_DEPTH);
+    let Err(err) = engine.quill_from_path(&deep) else {
+        panic!("inheritance deeper than the limit should fail");
+    };
+    let diag = &err.diagnostics()[0];
+    assert_eq!(diag.code.as_deref(), Some("quill::extends_too_deep"));
+    assert!(diag.message.contains("deep_0 -> "), "{}", diag.message);
+    assert!(
+        diag.message
+            .ends_with(&format!("deep_{}", MAX_EXPANSION_DEPTH)),
+        "{}",
+        diag.message
+    );
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_render_warns_on_empty_body() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "blank", "typst");
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let empty_body_warnings = |markdown: &str, allow_empty_body: bool| {
+        let parsed = Document::from_markdown(markdown).unwrap();
+        let result = quill
+            .render(
+                &parsed,
+                &RenderOptions::builder()
+                    .output_format(OutputFormat::Pdf)
+                    .allow_empty_body(allow_empty_body)
+                    .build(),
+            )
+            .expect("render failed");
+        assert!(result.artifacts[0].bytes.starts_with(b"%PDF"));
+        result
+            .warnings
+            .iter()
+            .filter(|w| w.code.as_deref() == Some("quill::empty_body"))
+            .count()
+    };
+
+    assert_eq!(
+        empty_body_warnings("---\nQUILL: blank\n---\n  \n", false),
+        1
+    );
+    assert_eq!(empty_body_warnings("---\nQUILL: blank\n---\n", true), 0);
+    assert_eq!(
+        empty_body_warnings("---\nQUILL: blank\n---\n\nSome text.\n", false),
+        0
+    );
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_referenced_assets_reports_present_and_missing() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "assets_quill", "typst");
+    fs::create_dir_all(quill_path.join("assets")).unwrap();
+    fs::write(quill_path.join("assets/present.txt"), "here").unwrap();
+    fs::write(
+        quill_path.join("plate.typ"),
+        "#read(\"assets/present.txt\")\n#image(\"assets/missing.png\")",
+    )
+    .unwrap();
+
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+    let referenced = quill.referenced_assets();
+    assert_eq!(referenced, vec!["assets/present.txt", "assets/missing.png"]);
+
+    let missing: Vec<_> = referenced
+        .iter()
+        .filter(|p| !quill.source().file_exists(p))
+        .collect();
+    assert_eq!(missing, vec!["assets/missing.png"]);
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_engine_render_one_shot() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "one_shot", "typst");
+    let parsed = Document::from_markdown("---\nQUILL: one_shot\n---\n").unwrap();
+
+    let result = Quillmark::new()
+        .render(
+            &quill_path,
+            &parsed,
+            &RenderOptions::builder()
+                .output_format(OutputFormat::Svg)
+                .build(),
+        )
+        .expect("one-shot render failed");
+    assert_eq!(result.output_format, OutputFormat::Svg);
+    assert_eq!(result.artifacts.len(), 1);
+}
+
+#[test]
+fn test_engine_render_one_shot_missing_quill() {
+    let temp_dir = TempDir::new().unwrap();
+    let parsed = Document::from_markdown("---\nQUILL: absent\n---\n").unwrap();
+
+    let result = Quillmark::new().render(
+        temp_dir.path().join("absent"),
+        &parsed,
+        &RenderOptions::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(quillmark::RenderError::QuillConfig { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "typst")]
+fn test_quill_parse_is_strict_when_card_types_declared() {
+    let temp_dir = TempDir::new().unwrap();
+    let quill_path = make_quill_dir(&temp_dir, "catalog", "typst");
+    let yaml = fs::read_to_string(quill_path.join("Quill.yaml")).unwrap();
+    fs::write(
+        quill_path.join("Quill.yaml"),
+        format!(
+            "{}card_types:\n  product:\n    fields:\n      name:\n        type: string\n",
+            yaml
+        ),
+    )
+    .unwrap();
+    let quill = Quillmark::new().quill_from_path(&quill_path).unwrap();
+
+    let ok = quill
+        .parse("---\nQUILL: catalog\n---\n\n---\nCARD: product\nname: Widget\n---\n")
+        .unwrap();
+    assert_eq!(ok.document.cards().len(), 1);
+
+    match quill.parse("---\nQUILL: catalog\n---\n\n---\nCARD: prodct\nname: Widget\n---\n") {
+        Err(quillmark::RenderError::InvalidFrontmatter { diag }) => {
+            assert_eq!(diag.code.as_deref(), Some("parse::unknown_card"));
+        }
+        other => panic!("Expected InvalidFrontmatter, got: {:?}", other),
+    }
+}